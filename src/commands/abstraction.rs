@@ -0,0 +1,133 @@
+use super::options::{available_threads, positional, seed_or_random, unknown, Options};
+use exam::poker::{
+    blocker_effect, combo_count, parse_hole_cards, parse_range, range_deal_probability, BucketSettings, EquityBuckets,
+    HandClass, HandIndexer,
+};
+
+// Canonical form of hole cards and a board under suit isomorphism, and the
+// dense index of its class for the street
+pub fn canonical(args: &[String]) -> Result<(), String> {
+    let hole_cards = parse_hole_cards(args.first().ok_or("canonical expects hole cards")?)?;
+    let mut board = Vec::new();
+
+    let mut options = Options::new(&args[1..]);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--board" => board = options.cards(option)?,
+            other => return Err(unknown(other)),
+        }
+    }
+
+    let indexer = match board.len() {
+        0 => HandIndexer::preflop(),
+        3 => HandIndexer::flop(),
+        4 => HandIndexer::turn(),
+        5 => HandIndexer::river(),
+        _ => return Err("The board must have 0, 3, 4 or 5 cards".to_string()),
+    };
+    let mut cards = hole_cards.to_vec();
+    cards.extend(&board);
+
+    let canonical = indexer.canonicalize(&cards)?;
+    let text: Vec<String> = canonical.iter().map(|card| card.to_string()).collect();
+    if board.is_empty() {
+        println!("Canonical: {}", text.join(" "));
+    } else {
+        println!("Canonical: {} | {}", text[..2].join(" "), text[2..].join(" "));
+    }
+    println!("Index: {} of {}", indexer.index(&cards)?, indexer.size());
+
+    Ok(())
+}
+
+// Clusters the hands of a street into equity buckets and saves them, or
+// looks up the bucket of a hand in a saved file
+pub fn buckets(args: &[String]) -> Result<(), String> {
+    if args.first().map(String::as_str) == Some("train") {
+        let path = args.get(1).ok_or("buckets train expects a file")?;
+        let mut settings = BucketSettings::new(
+            positional(args, 2, "buckets train expects a number of board cards")?,
+            positional(args, 3, "buckets train expects a number of buckets")?,
+        )?;
+        let mut hands = 10_000;
+        let mut seed = None;
+        let mut threads = available_threads();
+
+        let mut options = Options::new(&args[4..]);
+        while let Some(option) = options.next_option() {
+            match option {
+                "--hands" => hands = options.number(option)?,
+                "--bins" => settings = settings.with_bins(options.number(option)?)?,
+                "--runouts" => settings = settings.with_runouts(options.number(option)?)?,
+                "--seed" => seed = Some(options.number(option)?),
+                "--threads" => threads = options.number(option)?,
+                other => return Err(unknown(other)),
+            }
+        }
+
+        let buckets = EquityBuckets::train(settings, hands, seed_or_random(seed), threads)?;
+        buckets.save(path).map_err(|e| e.to_string())?;
+        for bucket in 0..settings.buckets {
+            println!("Bucket {}: {:.2}% mean equity", bucket, buckets.mean_equity(bucket) * 100.0);
+        }
+        println!("Wrote {} buckets to {}", settings.buckets, path);
+        return Ok(());
+    }
+
+    let path = args.first().ok_or("buckets expects a bucket file")?;
+    let buckets = EquityBuckets::load(path).map_err(|e| e.to_string())?;
+    let hole_cards = parse_hole_cards(args.get(1).ok_or("buckets expects hole cards")?)?;
+    let mut board = Vec::new();
+
+    let mut options = Options::new(&args[2..]);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--board" => board = options.cards(option)?,
+            other => return Err(unknown(other)),
+        }
+    }
+
+    let bucket = buckets.bucket(hole_cards, &board)?;
+    println!(
+        "Bucket {} of {} ({:.2}% mean equity)",
+        bucket,
+        buckets.settings().buckets,
+        buckets.mean_equity(bucket) * 100.0
+    );
+
+    Ok(())
+}
+
+// Combos of a range or class left given the board and the hero's hole
+// cards, what the hole cards block and the chance of being dealt it
+pub fn combos(args: &[String]) -> Result<(), String> {
+    let text = args.first().ok_or("combos expects a range")?;
+    let range = parse_range(text)?;
+    let mut board = Vec::new();
+    let mut hole_cards = None;
+
+    let mut options = Options::new(&args[1..]);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--board" => board = options.cards(option)?,
+            "--hole" => hole_cards = Some(options.hole_cards(option)?),
+            other => return Err(unknown(other)),
+        }
+    }
+
+    let mut known = board.clone();
+    if let Ok(class) = HandClass::parse(text) {
+        println!("{}: {} combos, {} with the board", class, combo_count(class, &[]), combo_count(class, &board));
+    }
+    if let Some(hole_cards) = hole_cards {
+        let effect = blocker_effect(&range, hole_cards, &board)?;
+        println!("Combos: {}", effect.remaining);
+        println!("Blocked by hole cards: {} ({:.2}%)", effect.blocked(), effect.blocked_share() * 100.0);
+        known.extend(hole_cards);
+    } else {
+        println!("Combos: {}", range.without(&board).total_weight());
+    }
+    println!("Probability of being dealt: {:.4}%", range_deal_probability(&range, &known) * 100.0);
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use super::options::{positional, unknown, Options};
+use exam::poker::{icm_equity, AllInSpot, EquitySource, PotOdds};
+
+// Pot odds and minimum defence frequency, and whether calling pays given an
+// equity, known or computed from the cards
+pub fn odds(args: &[String]) -> Result<(), String> {
+    let mut odds = PotOdds::new(
+        positional(args, 0, "odds expects the pot size")?,
+        positional(args, 1, "odds expects the amount to call")?,
+    )?;
+    let mut equity = None;
+    let mut hole_cards = None;
+    let mut villain = None;
+    let mut range = None;
+    let mut board = Vec::new();
+
+    let mut options = Options::new(&args[2..]);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--implied" => odds = odds.with_implied(options.amount(option)?)?,
+            "--equity" => equity = Some(options.value(option, "a number between 0 and 1")?),
+            "--hole" => hole_cards = Some(options.hole_cards(option)?),
+            "--villain" => villain = Some(options.hole_cards(option)?),
+            "--range" => range = Some(options.range(option)?),
+            "--board" => board = options.cards(option)?,
+            other => return Err(unknown(other)),
+        }
+    }
+
+    println!("Pot odds: {:.2} to 1", odds.ratio());
+    println!("Required equity: {:.2}%", odds.required_equity() * 100.0);
+    println!("Minimum defence frequency: {:.2}%", odds.minimum_defence_frequency() * 100.0);
+
+    let source = match (equity, hole_cards, villain, &range) {
+        (Some(equity), _, _, _) => EquitySource::Known(equity),
+        (None, Some(hole_cards), Some(villain), _) => EquitySource::AgainstHand { hole_cards, villain, board: &board },
+        (None, Some(hole_cards), None, Some(range)) => EquitySource::AgainstRange { hole_cards, villain: range, board: &board },
+        (None, Some(_), None, None) => return Err("--hole needs --villain or --range".to_string()),
+        _ => return Ok(()),
+    };
+    let analysis = odds.analyse(&source)?;
+    println!("Equity: {:.2}%", analysis.equity * 100.0);
+    println!("EV of calling: {:.2} - EV of folding: {:.2}", analysis.call_ev, analysis.fold_ev);
+    if analysis.equity > 0.0 {
+        println!("Implied winnings needed: {:.2}", odds.implied_needed(analysis.equity));
+    }
+    println!("{}", if analysis.should_call() { "Call" } else { "Fold" });
+
+    Ok(())
+}
+
+// Comma-separated amounts, e.g. "5000,3000,2000"
+fn parse_amounts(text: &str) -> Result<Vec<f64>, String> {
+    text.split(',')
+        .map(|amount| amount.trim().parse::<f64>().map_err(|_| format!("Invalid amount {}", amount)))
+        .collect()
+}
+
+// Prize equity of every player and, given a shove, the hero's ICM decision
+pub fn icm(args: &[String]) -> Result<(), String> {
+    let stacks = parse_amounts(args.first().ok_or("icm expects comma-separated stacks")?)?;
+    let payouts = parse_amounts(args.get(1).ok_or("icm expects comma-separated payouts")?)?;
+    let mut hero = None;
+    let mut villain = None;
+    let mut pot = 0.0;
+    let mut to_call = 0.0;
+    let mut equity = None;
+    let mut hole_cards = None;
+    let mut against = None;
+    let mut board = Vec::new();
+
+    // Players are numbered from 1 on the command line
+    let player = |options: &mut Options, option: &str| -> Result<usize, String> {
+        let player: usize = options.value(option, "a player number from 1")?;
+        player.checked_sub(1).ok_or_else(|| format!("{} expects a player number from 1", option))
+    };
+
+    let mut options = Options::new(&args[2..]);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--hero" => hero = Some(player(&mut options, option)?),
+            "--villain" => villain = Some(player(&mut options, option)?),
+            "--pot" => pot = options.amount(option)?,
+            "--to-call" => to_call = options.amount(option)?,
+            "--equity" => equity = Some(options.amount(option)?),
+            "--hole" => hole_cards = Some(options.hole_cards(option)?),
+            "--against" => against = Some(options.hole_cards(option)?),
+            "--board" => board = options.cards(option)?,
+            other => return Err(unknown(other)),
+        }
+    }
+
+    for (i, (stack, equity)) in stacks.iter().zip(icm_equity(&stacks, &payouts)?).enumerate() {
+        println!("Player {} ({}): {:.2}", i + 1, stack, equity);
+    }
+
+    let (Some(hero), Some(villain)) = (hero, villain) else {
+        return Ok(());
+    };
+    let source = match (equity, hole_cards, against) {
+        (Some(equity), _, _) => EquitySource::Known(equity),
+        (None, Some(hole_cards), Some(villain)) => EquitySource::AgainstHand { hole_cards, villain, board: &board },
+        _ => return Err("The decision needs --equity, or --hole and --against".to_string()),
+    };
+    let spot = AllInSpot { stacks, payouts, hero, villain, pot, to_call };
+    let decision = spot.decide(&source)?;
+    println!("Equity: {:.2}%", decision.equity * 100.0);
+    println!("Fold: {:.2} - call and win: {:.2} - call and lose: {:.2}", decision.fold, decision.call_win, decision.call_lose);
+    println!("Call: {:.2}", decision.call);
+    println!(
+        "Required equity: {:.2}% (chip EV: {:.2}%)",
+        decision.required_equity * 100.0,
+        decision.chip_required_equity * 100.0
+    );
+    println!("{}", if decision.should_call() { "Call" } else { "Fold" });
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use super::options::{unknown, Options};
+use exam::poker::{parse_cards, BoardTexture, NutRanking, Range};
+
+// Describes a flop, turn or river and the draws it offers
+pub fn texture(args: &[String]) -> Result<(), String> {
+    let board = parse_cards(args.first().ok_or("texture expects a board")?)?;
+    let texture = BoardTexture::classify(&board)?;
+
+    println!("Pairing: {:?}", texture.pairing);
+    println!("Suits: {:?}", texture.suits);
+    println!("Connectedness: {:?} ({} straight combos)", texture.connectedness, texture.straight_combos);
+    println!("Straight possible: {}", texture.straight_possible);
+    println!("Flush possible: {}", texture.flush_possible);
+    println!("Wetness: {:?}", texture.wetness);
+
+    for draw in &texture.flush_draws {
+        println!("Flush draw: {} hole card(s) of {:?}", draw.hole_cards_needed, draw.suit);
+    }
+    for draw in &texture.straight_draws {
+        let kind = if draw.is_open_ended() { "open-ended" } else { "gutshot" };
+        println!("Straight draw ({}): holding {:?}, completed by {:?}", kind, draw.hole_ranks, draw.outs);
+    }
+
+    Ok(())
+}
+
+// Holdings on a board from the nuts down, and where a given holding stands
+pub fn nuts(args: &[String]) -> Result<(), String> {
+    let board = parse_cards(args.first().ok_or("nuts expects a board")?)?;
+    let mut holding = None;
+    let mut top = 10;
+
+    let mut options = Options::new(&args[1..]);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--holding" => holding = Some(options.hole_cards(option)?),
+            "--top" => top = options.number(option)?,
+            other => return Err(unknown(other)),
+        }
+    }
+
+    let ranking = NutRanking::new(&board)?;
+    for (i, bucket) in ranking.buckets.iter().take(top).enumerate() {
+        let mut combos = Range::new();
+        for &combo in &bucket.combos {
+            combos.add_combo(combo, 1.0);
+        }
+        println!("{}. {:?} - {} combos: {}", i + 1, bucket.value.hand_type(), bucket.combos.len(), combos);
+    }
+
+    if let Some(holding) = holding {
+        let position = ranking.position(holding).ok_or("The holding shares a card with the board")?;
+        println!("{} {}: {}", holding[0], holding[1], position);
+    }
+
+    Ok(())
+}
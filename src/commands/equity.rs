@@ -0,0 +1,220 @@
+use super::options::{positional, seed_or_random, unknown, Options};
+use exam::poker::{
+    equity_vs_random, exact_equity_with_progress, exact_range_equity, find_outs, hand_strength, monte_carlo_equity,
+    monte_carlo_range_equity, parse_hole_cards, parse_range, precomputed_vs_random, HandClass, MAX_OPPONENTS,
+};
+use std::io::Write;
+
+// Equity of two or more Hold'em holdings, estimated or exactly enumerated
+pub fn equity(args: &[String]) -> Result<(), String> {
+    let mut holdings = Vec::new();
+    let mut board = Vec::new();
+    let mut dead = Vec::new();
+    let mut iterations = 100_000;
+    let mut seed = None;
+    let mut exact = false;
+
+    let mut options = Options::new(args);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--board" => board = options.cards(option)?,
+            "--dead" => dead = options.cards(option)?,
+            "--iterations" => iterations = options.number(option)?,
+            "--seed" => seed = Some(options.number(option)?),
+            "--exact" => exact = true,
+            hole_cards => holdings.push(parse_hole_cards(hole_cards)?),
+        }
+    }
+
+    let result = if exact {
+        // Progress goes to stderr so the results stay easy to parse
+        let result = exact_equity_with_progress(&holdings, &board, &dead, |done, total| {
+            eprint!("\rEnumerating boards: {}/{}", done, total);
+            let _ = std::io::stderr().flush();
+        })?;
+        eprintln!();
+        println!("Boards: {}", result.trials);
+        result
+    } else {
+        let result = monte_carlo_equity(&holdings, &board, &dead, iterations, seed_or_random(seed))?;
+        println!("Iterations: {}", result.trials);
+        result
+    };
+
+    for (i, (holding, player)) in holdings.iter().zip(&result.players).enumerate() {
+        println!(
+            "Player {} ({} {}): win {:.2}% - tie {:.2}% - lose {:.2}% - equity {:.2}% (± {:.2}%)",
+            i + 1,
+            holding[0],
+            holding[1],
+            player.win_rate() * 100.0,
+            player.tie_rate() * 100.0,
+            player.loss_rate() * 100.0,
+            player.equity * 100.0,
+            player.std_error * 100.0,
+        );
+    }
+
+    Ok(())
+}
+
+// Equity between weighted ranges, with the breakdown of the first (hero's) range
+pub fn range_equity(args: &[String]) -> Result<(), String> {
+    let mut ranges = Vec::new();
+    let mut board = Vec::new();
+    let mut dead = Vec::new();
+    let mut iterations = 100_000;
+    let mut seed = None;
+    let mut exact = false;
+
+    let mut options = Options::new(args);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--board" => board = options.cards(option)?,
+            "--dead" => dead = options.cards(option)?,
+            "--iterations" => iterations = options.number(option)?,
+            "--seed" => seed = Some(options.number(option)?),
+            "--exact" => exact = true,
+            range => ranges.push(parse_range(range)?),
+        }
+    }
+
+    let result = if exact {
+        let result = exact_range_equity(&ranges, &board, &dead)?;
+        println!("Boards: {}", result.trials);
+        result
+    } else {
+        let result = monte_carlo_range_equity(&ranges, &board, &dead, iterations, seed_or_random(seed))?;
+        println!("Iterations: {}", result.trials);
+        result
+    };
+
+    for (i, (range, equity)) in ranges.iter().zip(&result.ranges).enumerate() {
+        println!(
+            "Range {} ({}): win {:.2}% - tie {:.2}% - equity {:.2}% (± {:.2}%)",
+            i + 1,
+            range,
+            equity.win_rate * 100.0,
+            equity.tie_rate * 100.0,
+            equity.equity * 100.0,
+            equity.std_error * 100.0,
+        );
+    }
+
+    println!("Range 1 by combo:");
+    for combo in &result.hero_combos {
+        if combo.trials == 0 {
+            println!("{} {}: never dealt", combo.combo[0], combo.combo[1]);
+        } else {
+            println!("{} {}: equity {:.2}%", combo.combo[0], combo.combo[1], combo.equity * 100.0);
+        }
+    }
+
+    Ok(())
+}
+
+// Cards that improve a hand on the flop or turn, grouped by the hand they make
+pub fn outs(args: &[String]) -> Result<(), String> {
+    let hole_cards = parse_hole_cards(args.first().ok_or("outs expects hole cards")?)?;
+    let mut board = Vec::new();
+    let mut opponent = None;
+
+    let mut options = Options::new(&args[1..]);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--board" => board = options.cards(option)?,
+            "--against" => opponent = Some(options.hole_cards(option)?),
+            other => return Err(unknown(other)),
+        }
+    }
+
+    let outs = find_outs(hole_cards, &board, opponent)?;
+    println!("Outs: {} of {} unseen cards", outs.count(), outs.unseen);
+    for (hand_type, cards) in &outs.groups {
+        let cards: Vec<String> = cards.iter().map(|card| card.to_string()).collect();
+        println!("{:?} ({}): {}", hand_type, cards.len(), cards.join(" "));
+    }
+    println!("Next card: {:.2}%", outs.next_card_probability() * 100.0);
+    if outs.cards_to_come > 1 {
+        println!("By the river: {:.2}%", outs.by_river_probability() * 100.0);
+    }
+
+    Ok(())
+}
+
+// Chances against random hands, simulated or read from the precomputed table
+pub fn vs_random(args: &[String]) -> Result<(), String> {
+    if args.first().map(String::as_str) == Some("table") {
+        let class = HandClass::parse(args.get(1).ok_or("vs-random table expects a hand class")?)?;
+        println!("{} against random hands:", class);
+        for opponents in 1..=MAX_OPPONENTS {
+            let odds = precomputed_vs_random(class, opponents)?;
+            println!(
+                "{} opponent{}: win {:.2}% - tie {:.2}%",
+                opponents,
+                if opponents == 1 { "" } else { "s" },
+                odds.win_rate * 100.0,
+                odds.tie_rate * 100.0,
+            );
+        }
+        return Ok(());
+    }
+
+    let hole_cards = parse_hole_cards(args.first().ok_or("vs-random expects hole cards")?)?;
+    let opponents: usize = positional(args, 1, "vs-random expects a number of opponents")?;
+    let mut board = Vec::new();
+    let mut iterations = 100_000;
+    let mut seed = None;
+
+    let mut options = Options::new(&args[2..]);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--board" => board = options.cards(option)?,
+            "--iterations" => iterations = options.number(option)?,
+            "--seed" => seed = Some(options.number(option)?),
+            other => return Err(unknown(other)),
+        }
+    }
+
+    let seed = seed_or_random(seed);
+    println!("Iterations: {}", iterations);
+    let result = equity_vs_random(hole_cards, &board, opponents, iterations, seed)?;
+    println!(
+        "{} {} against {} random hand{}: win {:.2}% - tie {:.2}% - lose {:.2}% - equity {:.2}% (± {:.2}%)",
+        hole_cards[0],
+        hole_cards[1],
+        opponents,
+        if opponents == 1 { "" } else { "s" },
+        result.win_rate() * 100.0,
+        result.tie_rate() * 100.0,
+        result.loss_rate() * 100.0,
+        result.equity * 100.0,
+        result.std_error * 100.0,
+    );
+
+    Ok(())
+}
+
+// Hand strength and potentials against every holding, or a range of them
+pub fn strength(args: &[String]) -> Result<(), String> {
+    let hole_cards = parse_hole_cards(args.first().ok_or("strength expects hole cards")?)?;
+    let mut board = Vec::new();
+    let mut range = None;
+
+    let mut options = Options::new(&args[1..]);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--board" => board = options.cards(option)?,
+            "--range" => range = Some(options.range(option)?),
+            other => return Err(unknown(other)),
+        }
+    }
+
+    let result = hand_strength(hole_cards, &board, range.as_ref())?;
+    println!("HS: {:.2}%", result.hand_strength * 100.0);
+    println!("PPot: {:.2}%", result.positive_potential * 100.0);
+    println!("NPot: {:.2}%", result.negative_potential * 100.0);
+    println!("EHS: {:.2}%", result.effective_strength * 100.0);
+
+    Ok(())
+}
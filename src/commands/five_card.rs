@@ -0,0 +1,132 @@
+use super::options::{positional, seed_or_random, unknown, Options};
+use exam::poker::{discard_options, hand_percentile, parse_hand, Deck, Hand, HandType, Paytable, Rng};
+
+// Deals random hands, each from a freshly shuffled deck without the excluded cards
+pub fn deal(args: &[String]) -> Result<(), String> {
+    let count: usize = positional(args, 0, "deal expects the number of hands to deal")?;
+
+    let mut seed = None;
+    let mut excluded = Vec::new();
+    let mut evaluate = false;
+    let mut rank = false;
+
+    let mut options = Options::new(&args[1..]);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--seed" => seed = Some(options.number(option)?),
+            "--exclude" => excluded = options.cards(option)?,
+            "--evaluate" => evaluate = true,
+            "--rank" => rank = true,
+            other => return Err(unknown(other)),
+        }
+    }
+
+    if excluded.len() > 47 {
+        return Err("Too many excluded cards to deal a hand".to_string());
+    }
+
+    let mut rng = Rng::new(seed_or_random(seed));
+    let mut hands: Vec<Hand> = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let mut deck = Deck::new();
+        deck.remove(&excluded)?;
+        deck.shuffle(&mut rng);
+        let hand = deck.deal_hand()?;
+
+        if evaluate {
+            println!("{} - {:?}", hand, hand.evaluate());
+        } else {
+            println!("{}", hand);
+        }
+        hands.push(hand);
+    }
+
+    if rank {
+        hands.sort_by(|a, b| b.cmp(a));
+        println!("Ranking:");
+
+        let mut position = 0;
+        for (i, hand) in hands.iter().enumerate() {
+            // Equal hands share the same position
+            if i == 0 || hands[i - 1] > *hand {
+                position = i + 1;
+            }
+            println!("{}. {} - {:?}", position, hand, hand.evaluate());
+        }
+    }
+
+    Ok(())
+}
+
+// Paytables by their command-line name
+fn paytable_named(name: &str) -> Result<Paytable, String> {
+    match name {
+        "jacks-or-better" => Ok(Paytable::jacks_or_better()),
+        "bonus-poker" => Ok(Paytable::bonus_poker()),
+        "deuces-wild" => Ok(Paytable::deuces_wild()),
+        _ => Err(format!("Unknown paytable {}", name)),
+    }
+}
+
+// Five-card draw discards ranked by value, or by chance of beating a known hand
+pub fn draw(args: &[String]) -> Result<(), String> {
+    let hand = parse_hand(args.first().ok_or("draw expects a hand")?)?;
+    let mut paytable = Paytable::jacks_or_better();
+    let mut opponent = None;
+    let mut top = 5;
+
+    let mut options = Options::new(&args[1..]);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--against" => opponent = Some(options.hand(option)?),
+            "--paytable" => paytable = paytable_named(&options.value::<String>(option, "a paytable name")?)?,
+            "--top" => top = options.number(option)?,
+            other => return Err(unknown(other)),
+        }
+    }
+
+    let choices = discard_options(&hand, &paytable, opponent.as_ref())?;
+
+    for (i, choice) in choices.iter().take(top).enumerate() {
+        let discarded: Vec<String> = hand
+            .cards
+            .iter()
+            .zip(choice.discard)
+            .filter(|&(_, discarded)| discarded)
+            .map(|(card, _)| card.to_string())
+            .collect();
+        let discarded = if discarded.is_empty() { "nothing".to_string() } else { discarded.join(" ") };
+
+        let mut line = format!("{}. Discard {}: EV {:.4} ({})", i + 1, discarded, choice.expected_value, paytable.name);
+        if let Some(beat) = choice.beat_probability {
+            line += &format!(" - beats opponent {:.2}%", beat * 100.0);
+        }
+        println!("{}", line);
+
+        let distribution: Vec<String> = HandType::ALL
+            .iter()
+            .filter(|&&hand_type| choice.probability(hand_type) > 0.0)
+            .map(|&hand_type| format!("{:?} {:.2}%", hand_type, choice.probability(hand_type) * 100.0))
+            .collect();
+        println!("   {}", distribution.join(", "));
+    }
+
+    Ok(())
+}
+
+// Exact standing of a five-card hand among all possible hands
+pub fn percentile(args: &[String]) -> Result<(), String> {
+    let hand = parse_hand(args.first().ok_or("percentile expects a hand")?)?;
+    let standing = hand_percentile(&hand)?;
+
+    println!("{} - {:?}", hand, hand.evaluate());
+    println!("Value rank: {} of 7462", standing.value_rank);
+    println!(
+        "Better hands: {} - equal hands: {} - worse hands: {}",
+        standing.better_hands, standing.equal_hands, standing.worse_hands
+    );
+    println!("Beats {:.4}% of hands", standing.percentile() * 100.0);
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+// The subcommands of the command line, one module per theme
+pub mod abstraction;
+pub mod betting;
+pub mod board;
+pub mod equity;
+pub mod five_card;
+pub mod options;
+pub mod preflop;
+
+pub use abstraction::{buckets, canonical, combos};
+pub use betting::{icm, odds};
+pub use board::{nuts, texture};
+pub use equity::{equity, outs, range_equity, strength, vs_random};
+pub use five_card::{deal, draw, percentile};
+pub use preflop::{preflop, push_fold, starting};
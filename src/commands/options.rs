@@ -0,0 +1,77 @@
+use exam::poker::{parse_cards, parse_hand, parse_hole_cards, parse_range, Card, Hand, Range, Rng};
+use std::str::FromStr;
+
+// The flags following a subcommand's positional arguments, read one at a
+// time so each subcommand only matches the names it knows
+pub struct Options<'a> {
+    args: std::slice::Iter<'a, String>,
+}
+
+impl<'a> Options<'a> {
+    pub fn new(args: &'a [String]) -> Options<'a> {
+        Options { args: args.iter() }
+    }
+
+    // The next flag, or positional argument for subcommands taking a list
+    pub fn next_option(&mut self) -> Option<&'a str> {
+        self.args.next().map(String::as_str)
+    }
+
+    // The value following a flag, parsed, e.g. "--seed expects a number"
+    pub fn value<T: FromStr>(&mut self, option: &str, expects: &str) -> Result<T, String> {
+        self.args
+            .next()
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| format!("{} expects {}", option, expects))
+    }
+
+    pub fn number<T: FromStr>(&mut self, option: &str) -> Result<T, String> {
+        self.value(option, "a number")
+    }
+
+    pub fn amount(&mut self, option: &str) -> Result<f64, String> {
+        self.value(option, "an amount")
+    }
+
+    pub fn cards(&mut self, option: &str) -> Result<Vec<Card>, String> {
+        let value = self.args.next().ok_or_else(|| format!("{} expects a list of cards", option))?;
+        Ok(parse_cards(value)?)
+    }
+
+    pub fn hole_cards(&mut self, option: &str) -> Result<[Card; 2], String> {
+        let value = self.args.next().ok_or_else(|| format!("{} expects hole cards", option))?;
+        Ok(parse_hole_cards(value)?)
+    }
+
+    pub fn hand(&mut self, option: &str) -> Result<Hand, String> {
+        let value = self.args.next().ok_or_else(|| format!("{} expects a hand", option))?;
+        Ok(parse_hand(value)?)
+    }
+
+    pub fn range(&mut self, option: &str) -> Result<Range, String> {
+        let value = self.args.next().ok_or_else(|| format!("{} expects a range", option))?;
+        Ok(parse_range(value)?)
+    }
+}
+
+pub fn unknown(option: &str) -> String {
+    format!("Unknown option {}", option)
+}
+
+// A positional argument parsed, e.g. the number of hands to deal
+pub fn positional<T: FromStr>(args: &[String], index: usize, error: &str) -> Result<T, String> {
+    args.get(index).and_then(|value| value.parse().ok()).ok_or_else(|| error.to_string())
+}
+
+// The --seed value or one from the clock, always reported so a run can be
+// reproduced
+pub fn seed_or_random(seed: Option<u64>) -> u64 {
+    let seed = seed.unwrap_or_else(|| Rng::from_time().next_u64());
+    println!("Seed: {}", seed);
+    seed
+}
+
+// Default for --threads
+pub fn available_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |threads| threads.get())
+}
@@ -0,0 +1,115 @@
+use super::options::{available_threads, positional, seed_or_random, unknown, Options};
+use exam::poker::{
+    parse_hole_cards, solve_heads_up, starting_hand_ranking, starting_hand_rankings, HandClass, PreflopTable,
+};
+
+// Generates the pre-flop equity table, or looks hand classes up in it
+pub fn preflop(args: &[String]) -> Result<(), String> {
+    if args.first().map(String::as_str) == Some("generate") {
+        let path = args.get(1).ok_or("preflop generate expects a file")?;
+        let mut trials = 20_000;
+        let mut seed = None;
+        let mut threads = available_threads();
+
+        let mut options = Options::new(&args[2..]);
+        while let Some(option) = options.next_option() {
+            match option {
+                "--trials" => trials = options.number(option)?,
+                "--seed" => seed = Some(options.number(option)?),
+                "--threads" => threads = options.number(option)?,
+                other => return Err(unknown(other)),
+            }
+        }
+
+        let table = PreflopTable::generate(trials, seed_or_random(seed), threads)?;
+        table.save(path).map_err(|e| e.to_string())?;
+        println!("Wrote {} trials per matchup to {}", trials, path);
+        return Ok(());
+    }
+
+    let path = args.first().ok_or("preflop expects a table file")?;
+    let table = PreflopTable::load(path).map_err(|e| e.to_string())?;
+    let hero = HandClass::parse(args.get(1).ok_or("preflop expects a hand class")?)?;
+    match args.get(2) {
+        Some(villain) => {
+            let villain = HandClass::parse(villain)?;
+            println!("{} vs {}: {:.2}%", hero, villain, table.equity(hero, villain) * 100.0);
+        },
+        None => println!("{} vs a random hand: {:.2}%", hero, table.equity_vs_random(hero) * 100.0),
+    }
+
+    Ok(())
+}
+
+// Heads-up push/fold equilibrium charts from a pre-flop equity table file
+pub fn push_fold(args: &[String]) -> Result<(), String> {
+    let path = args.first().ok_or("push-fold expects a table file")?;
+    let stack: f64 = positional(args, 1, "push-fold expects a stack in big blinds")?;
+    let mut ante = 0.0;
+
+    let mut options = Options::new(&args[2..]);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--ante" => ante = options.value(option, "an amount in big blinds")?,
+            other => return Err(unknown(other)),
+        }
+    }
+
+    let table = PreflopTable::load(path).map_err(|e| e.to_string())?;
+    let solution = solve_heads_up(&table, stack, ante)?;
+    println!("Stack: {} BB - ante: {} BB", stack, ante);
+    println!("Small blind shoves {:.1}% of hands:", solution.shove_percentage() * 100.0);
+    print!("{}", solution.shove_chart());
+    println!("Big blind calls {:.1}% of hands:", solution.call_percentage() * 100.0);
+    print!("{}", solution.call_chart());
+    println!("Small blind EV: {:.3} BB per hand (exploitability {:.4} BB)", solution.shover_ev, solution.exploitability);
+
+    Ok(())
+}
+
+// Chen score, Sklansky-Malmuth group and equity rank of a starting hand, or
+// the best classes by equity
+pub fn starting(args: &[String]) -> Result<(), String> {
+    let mut hole_cards = None;
+    let mut opponents = 1;
+    let mut top = 169;
+
+    let mut options = Options::new(args);
+    while let Some(option) = options.next_option() {
+        match option {
+            "--opponents" => opponents = options.number(option)?,
+            "--top" => top = options.number(option)?,
+            cards => hole_cards = Some(parse_hole_cards(cards)?),
+        }
+    }
+
+    let group = |group: Option<u8>| group.map_or("-".to_string(), |group| group.to_string());
+    if let Some(hole_cards) = hole_cards {
+        let ranking = starting_hand_ranking(hole_cards, opponents)?;
+        println!("{} {} ({})", hole_cards[0], hole_cards[1], ranking.class);
+        println!("Chen score: {}", ranking.chen_score);
+        println!("Sklansky group: {}", group(ranking.sklansky_group));
+        println!(
+            "Equity against {} random hand{}: {:.2}% - rank {} of 169",
+            opponents,
+            if opponents == 1 { "" } else { "s" },
+            ranking.equity * 100.0,
+            ranking.equity_rank
+        );
+        return Ok(());
+    }
+
+    println!("Rank  Class  Equity  Chen  Group");
+    for ranking in starting_hand_rankings(opponents)?.iter().take(top) {
+        println!(
+            "{:>4}  {:<5}  {:>5.2}%  {:>4}  {:>5}",
+            ranking.equity_rank,
+            ranking.class.to_string(),
+            ranking.equity * 100.0,
+            ranking.chen_score,
+            group(ranking.sklansky_group)
+        );
+    }
+
+    Ok(())
+}
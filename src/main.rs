@@ -1,3 +1,5 @@
+mod commands;
+
 use exam::poker::parse_hand;
 use std::env;
 
@@ -6,7 +8,34 @@ fn main() {
     
     let args: Vec<String> = env::args().collect();
     
-    if args.len() == 3 {
+    // Subcommands, if the first argument names one
+    let outcome = match args.get(1).map(String::as_str) {
+        Some("deal") => Some(commands::deal(&args[2..])),
+        Some("equity") => Some(commands::equity(&args[2..])),
+        Some("range-equity") => Some(commands::range_equity(&args[2..])),
+        Some("outs") => Some(commands::outs(&args[2..])),
+        Some("draw") => Some(commands::draw(&args[2..])),
+        Some("preflop") => Some(commands::preflop(&args[2..])),
+        Some("texture") => Some(commands::texture(&args[2..])),
+        Some("nuts") => Some(commands::nuts(&args[2..])),
+        Some("percentile") => Some(commands::percentile(&args[2..])),
+        Some("vs-random") => Some(commands::vs_random(&args[2..])),
+        Some("strength") => Some(commands::strength(&args[2..])),
+        Some("odds") => Some(commands::odds(&args[2..])),
+        Some("icm") => Some(commands::icm(&args[2..])),
+        Some("push-fold") => Some(commands::push_fold(&args[2..])),
+        Some("starting") => Some(commands::starting(&args[2..])),
+        Some("canonical") => Some(commands::canonical(&args[2..])),
+        Some("buckets") => Some(commands::buckets(&args[2..])),
+        Some("combos") => Some(commands::combos(&args[2..])),
+        _ => None,
+    };
+
+    if let Some(outcome) = outcome {
+        if let Err(e) = outcome {
+            println!("Error: {}", e);
+        }
+    } else if args.len() == 3 {
         // User provided two hands as arguments
         let hand1_str = &args[1];
         let hand2_str = &args[2];
@@ -31,6 +60,27 @@ fn main() {
         // No arguments provided, just show usage information
         println!("No hands provided as arguments.");
         println!("Usage: cargo run -- <hand1> <hand2>");
+        println!("       cargo run -- deal <count> [--seed <n>] [--exclude <cards>] [--evaluate] [--rank]");
+        println!("       cargo run -- equity <hole cards> <hole cards>... [--board <cards>] [--dead <cards>] [--iterations <n>] [--seed <n>] [--exact]");
+        println!("       cargo run -- outs <hole cards> --board <cards> [--against <hole cards>]");
+        println!("       cargo run -- draw <hand> [--paytable <name>] [--against <hand>] [--top <n>]");
+        println!("       cargo run -- preflop generate <file> [--trials <n>] [--seed <n>] [--threads <n>]");
+        println!("       cargo run -- preflop <file> <hand class> [<hand class>]");
+        println!("       cargo run -- texture <board>");
+        println!("       cargo run -- nuts <board> [--holding <hole cards>] [--top <n>]");
+        println!("       cargo run -- percentile <hand>");
+        println!("       cargo run -- vs-random <hole cards> <opponents> [--board <cards>] [--iterations <n>] [--seed <n>]");
+        println!("       cargo run -- vs-random table <hand class>");
+        println!("       cargo run -- strength <hole cards> --board <cards> [--range <range>]");
+        println!("       cargo run -- odds <pot> <to call> [--implied <amount>] [--equity <0-1> | --hole <hole cards> (--villain <hole cards> | --range <range>) [--board <cards>]]");
+        println!("       cargo run -- icm <stacks> <payouts> [--hero <n> --villain <n> --pot <chips> --to-call <chips> (--equity <0-1> | --hole <hole cards> --against <hole cards> [--board <cards>])]");
+        println!("       cargo run -- push-fold <file> <stack in big blinds> [--ante <big blinds>]");
+        println!("       cargo run -- starting [<hole cards>] [--opponents <n>] [--top <n>]");
+        println!("       cargo run -- canonical <hole cards> [--board <cards>]");
+        println!("       cargo run -- buckets train <file> <board cards> <buckets> [--hands <n>] [--bins <n>] [--runouts <n>] [--seed <n>] [--threads <n>]");
+        println!("       cargo run -- buckets <file> <hole cards> [--board <cards>]");
+        println!("       cargo run -- combos <range> [--board <cards>] [--hole <cards>]");
+        println!("       cargo run -- range-equity <range> <range>... [--board <cards>] [--dead <cards>] [--iterations <n>] [--seed <n>] [--exact]");
         println!("Example: cargo run -- \"AS KS QS JS 10S\" \"AH AD AC AS KH\"");
     }
 }
\ No newline at end of file
@@ -0,0 +1,193 @@
+use super::card::Card;
+use super::equity::exact_equity;
+use super::range::Range;
+use super::range_equity::exact_range_equity;
+
+// Where the hero's share of the pot comes from
+#[derive(Debug, PartialEq, Clone)]
+pub enum EquitySource<'a> {
+    // Already known, between 0 and 1
+    Known(f64),
+    // Exact all-in equity against the opponent's hole cards
+    AgainstHand { hole_cards: [Card; 2], villain: [Card; 2], board: &'a [Card] },
+    // Exact all-in equity against a weighted range; slow before the flop
+    AgainstRange { hole_cards: [Card; 2], villain: &'a Range, board: &'a [Card] },
+}
+
+impl EquitySource<'_> {
+    pub fn equity(&self) -> Result<f64, &'static str> {
+        match self {
+            EquitySource::Known(equity) => {
+                if (0.0..=1.0).contains(equity) {
+                    Ok(*equity)
+                } else {
+                    Err("Equity must be between 0 and 1")
+                }
+            }
+            EquitySource::AgainstHand { hole_cards, villain, board } => {
+                Ok(exact_equity(&[*hole_cards, *villain], board, &[])?.players[0].equity)
+            }
+            EquitySource::AgainstRange { hole_cards, villain, board } => {
+                let mut hero = Range::new();
+                hero.add_combo(*hole_cards, 1.0);
+                let ranges = [hero, (*villain).clone()];
+                Ok(exact_range_equity(&ranges, board, &[])?.ranges[0].equity)
+            }
+        }
+    }
+}
+
+// A decision facing a bet. `pot` is everything in the middle, the bet
+// included; `implied` is what the hero expects to win on later streets when
+// the hand holds up.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PotOdds {
+    pub pot: f64,
+    pub to_call: f64,
+    pub implied: f64,
+}
+
+// Expected values of calling and folding, counted from the moment of the
+// decision, so folding is always worth 0
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CallAnalysis {
+    pub equity: f64,
+    pub required_equity: f64,
+    pub call_ev: f64,
+    pub fold_ev: f64,
+}
+
+impl CallAnalysis {
+    pub fn should_call(&self) -> bool {
+        self.call_ev > self.fold_ev
+    }
+}
+
+impl PotOdds {
+    pub fn new(pot: f64, to_call: f64) -> Result<Self, &'static str> {
+        if !(pot.is_finite() && to_call.is_finite() && pot >= 0.0 && to_call > 0.0) {
+            return Err("The pot can't be negative and the amount to call must be positive");
+        }
+        Ok(PotOdds { pot, to_call, implied: 0.0 })
+    }
+
+    pub fn with_implied(self, implied: f64) -> Result<Self, &'static str> {
+        if !(implied.is_finite() && implied >= 0.0) {
+            return Err("Implied winnings can't be negative");
+        }
+        Ok(PotOdds { implied, ..self })
+    }
+
+    // The pot is laid `ratio` to 1, e.g. 3 for a half-pot bet
+    pub fn ratio(&self) -> f64 {
+        self.pot / self.to_call
+    }
+
+    // Share of the final pot the call pays for; implied winnings lower it
+    pub fn required_equity(&self) -> f64 {
+        self.to_call / (self.pot + self.implied + self.to_call)
+    }
+
+    pub fn call_ev(&self, equity: f64) -> f64 {
+        equity * (self.pot + self.implied) - (1.0 - equity) * self.to_call
+    }
+
+    // Future winnings needed for a call with `equity` to break even, 0 when
+    // the pot already lays the price
+    pub fn implied_needed(&self, equity: f64) -> f64 {
+        if equity <= 0.0 {
+            return f64::INFINITY;
+        }
+        ((1.0 - equity) * self.to_call / equity - self.pot).max(0.0)
+    }
+
+    // Share of hands to continue with so that a bluff of this size can't
+    // profit at once, assuming the amount to call is the opponent's whole bet
+    pub fn minimum_defence_frequency(&self) -> f64 {
+        if self.pot <= self.to_call {
+            return 0.0;
+        }
+        (self.pot - self.to_call) / self.pot
+    }
+
+    pub fn analyse(&self, source: &EquitySource) -> Result<CallAnalysis, &'static str> {
+        let equity = source.equity()?;
+        Ok(CallAnalysis {
+            equity,
+            required_equity: self.required_equity(),
+            call_ev: self.call_ev(equity),
+            fold_ev: 0.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::{parse_cards, parse_hole_cards};
+    use super::super::range::parse_range;
+
+    #[test]
+    fn test_pot_odds() {
+        // A pot-sized bet: 100 in the pot, 100 bet, 100 to call
+        let odds = PotOdds::new(200.0, 100.0).unwrap();
+        assert_eq!(odds.ratio(), 2.0);
+        assert!((odds.required_equity() - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(odds.minimum_defence_frequency(), 0.5);
+        assert!(odds.call_ev(1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(odds.call_ev(0.5), 50.0);
+
+        // Half pot: 25% to call, 67% to defend
+        let odds = PotOdds::new(150.0, 50.0).unwrap();
+        assert_eq!(odds.required_equity(), 0.25);
+        assert!((odds.minimum_defence_frequency() - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_implied_odds() {
+        // A flush draw on the turn, about 1 in 5, facing a pot-sized bet
+        let odds = PotOdds::new(200.0, 100.0).unwrap();
+        let equity = 0.2;
+        assert!(odds.call_ev(equity) < 0.0);
+        assert!((odds.implied_needed(equity) - 200.0).abs() < 1e-9);
+
+        let odds = odds.with_implied(200.0).unwrap();
+        assert!((odds.required_equity() - 0.2).abs() < 1e-12);
+        assert!(odds.call_ev(equity).abs() < 1e-9);
+        assert_eq!(PotOdds::new(200.0, 100.0).unwrap().implied_needed(0.5), 0.0);
+    }
+
+    #[test]
+    fn test_equity_from_cards() {
+        let board = parse_cards("KH 7H 2C 9S").unwrap();
+        let odds = PotOdds::new(150.0, 50.0).unwrap();
+
+        // Nine hearts out of 44 cards against a set, but the deuce and nine
+        // of hearts fill it up
+        let draw = EquitySource::AgainstHand {
+            hole_cards: parse_hole_cards("AH 5H").unwrap(),
+            villain: parse_hole_cards("7S 7C").unwrap(),
+            board: &board,
+        };
+        let analysis = odds.analyse(&draw).unwrap();
+        assert!((analysis.equity - 7.0 / 44.0).abs() < 1e-12);
+        assert!(!analysis.should_call());
+
+        let range = parse_range("77").unwrap();
+        let against_range = EquitySource::AgainstRange {
+            hole_cards: parse_hole_cards("AH 5H").unwrap(),
+            villain: &range,
+            board: &board,
+        };
+        assert!((odds.analyse(&against_range).unwrap().equity - 7.0 / 44.0).abs() < 1e-12);
+        assert!(odds.analyse(&EquitySource::Known(0.3)).unwrap().should_call());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(PotOdds::new(-1.0, 10.0).is_err());
+        assert!(PotOdds::new(10.0, 0.0).is_err());
+        assert!(PotOdds::new(10.0, 5.0).unwrap().with_implied(-5.0).is_err());
+        assert!(EquitySource::Known(1.5).equity().is_err());
+    }
+}
@@ -0,0 +1,484 @@
+use super::card::Card;
+use super::combinations::{binomial, for_each_combination};
+use super::deck::Deck;
+use super::isomorphism::HandIndexer;
+use super::rng::Rng;
+use super::simulation::Simulation;
+use super::value::HandValue;
+use std::cmp::Ordering;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// File layout: magic, version, board cards (u8), bins (u16), runouts (u32),
+// seed (u64), buckets (u32), then every centroid bin as a little-endian f64,
+// bucket by bucket
+const MAGIC: &[u8; 4] = b"EQBK";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 24;
+
+// Lloyd iterations stop earlier once no hand changes bucket
+const MAX_ITERATIONS: usize = 100;
+
+// How hands of one street are described and grouped
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BucketSettings {
+    // 0 before the flop, 3, 4 or 5 after
+    pub board_cards: usize,
+    pub buckets: usize,
+    // Equity bins of the histograms
+    pub bins: usize,
+    // Board completions dealt per hand; all of them when there are fewer
+    pub runouts: u32,
+}
+
+impl BucketSettings {
+    pub fn new(board_cards: usize, buckets: usize) -> Result<Self, &'static str> {
+        if ![0, 3, 4, 5].contains(&board_cards) {
+            return Err("The board must have 0, 3, 4 or 5 cards");
+        }
+        if buckets == 0 {
+            return Err("At least one bucket is needed");
+        }
+        Ok(BucketSettings { board_cards, buckets, bins: 10, runouts: 50 })
+    }
+
+    pub fn with_bins(self, bins: usize) -> Result<Self, &'static str> {
+        if bins == 0 || bins > u16::MAX as usize {
+            return Err("Between 1 and 65535 bins");
+        }
+        Ok(BucketSettings { bins, ..self })
+    }
+
+    pub fn with_runouts(self, runouts: u32) -> Result<Self, &'static str> {
+        if runouts == 0 {
+            return Err("At least one runout is needed");
+        }
+        Ok(BucketSettings { runouts, ..self })
+    }
+
+    fn indexer(&self) -> HandIndexer {
+        if self.board_cards == 0 {
+            HandIndexer::preflop()
+        } else {
+            HandIndexer::new(&[2, self.board_cards]).unwrap()
+        }
+    }
+}
+
+// Equity of `hole_cards` on a complete board against every other holding,
+// ties counting half
+fn river_equity(hole_cards: [Card; 2], board: &[Card], unseen: &[Card]) -> f64 {
+    let mut cards = [hole_cards[0]; 7];
+    cards[..2].copy_from_slice(&hole_cards);
+    cards[2..].copy_from_slice(board);
+    let hero = HandValue::from_cards(&cards);
+
+    let (mut points, mut holdings) = (0, 0);
+    for_each_combination(unseen, 2, |villain| {
+        cards[..2].copy_from_slice(villain);
+        points += match hero.cmp(&HandValue::from_cards(&cards)) {
+            Ordering::Greater => 2,
+            Ordering::Equal => 1,
+            Ordering::Less => 0,
+        };
+        holdings += 1;
+    });
+    points as f64 / (2 * holdings) as f64
+}
+
+// Distribution of the river equity against a random holding over board
+// completions, in `bins` equal bins. Every completion is dealt when there are
+// no more than `runouts` of them, so on the river the histogram is a single
+// bin and on the turn it is exact for 46 runouts or more.
+pub fn equity_histogram(
+    hole_cards: [Card; 2],
+    board: &[Card],
+    bins: usize,
+    runouts: u32,
+    rng: &mut Rng,
+) -> Result<Vec<f64>, &'static str> {
+    if board.len() > 5 || (1..3).contains(&board.len()) {
+        return Err("The board must have 0, 3, 4 or 5 cards");
+    }
+    if bins == 0 || runouts == 0 {
+        return Err("At least one bin and one runout are needed");
+    }
+    let mut deck = Deck::new();
+    deck.remove(&hole_cards).map_err(|_| "Duplicate card")?;
+    deck.remove(board).map_err(|_| "Duplicate card")?;
+    let mut unseen = deck.cards().to_vec();
+
+    let missing = 5 - board.len();
+    let mut histogram = vec![0.0; bins];
+    let mut full_board = [hole_cards[0]; 5];
+    full_board[..board.len()].copy_from_slice(board);
+    let mut add = |full_board: &[Card], unseen: &[Card]| {
+        let equity = river_equity(hole_cards, full_board, unseen);
+        histogram[((equity * bins as f64) as usize).min(bins - 1)] += 1.0;
+    };
+
+    if binomial(unseen.len(), missing) <= runouts as u64 {
+        let all = unseen.clone();
+        for_each_combination(&all, missing, |runout| {
+            full_board[board.len()..].copy_from_slice(runout);
+            unseen.retain(|card| !runout.contains(card));
+            add(&full_board, &unseen);
+            unseen.clone_from(&all);
+        });
+    } else {
+        for _ in 0..runouts {
+            // The runout is moved to the end, the rest stays unseen
+            for i in 0..missing {
+                let last = unseen.len() - 1 - i;
+                let drawn = rng.below(last + 1);
+                unseen.swap(drawn, last);
+            }
+            let split = unseen.len() - missing;
+            full_board[board.len()..].copy_from_slice(&unseen[split..]);
+            add(&full_board, &unseen[..split]);
+        }
+    }
+
+    let total: f64 = histogram.iter().sum();
+    Ok(histogram.into_iter().map(|count| count / total).collect())
+}
+
+// Squared distance between the cumulative histograms, so that moving weight
+// to a nearby bin costs less than moving it across the equity scale
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    let mut gap = 0.0;
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| {
+            gap += a - b;
+            gap * gap
+        })
+        .sum()
+}
+
+fn nearest(centroids: &[Vec<f64>], point: &[f64]) -> usize {
+    centroids
+        .iter()
+        .map(|centroid| distance(centroid, point))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(bucket, _)| bucket)
+        .unwrap()
+}
+
+// Lloyd's k-means, seeded with k-means++: each new centroid is a point drawn
+// with a chance proportional to its distance to the closest centroid so far
+fn kmeans(points: &[Vec<f64>], k: usize, rng: &mut Rng) -> Result<Vec<Vec<f64>>, &'static str> {
+    let mut centroids = vec![points[rng.below(points.len())].clone()];
+    let mut closest: Vec<f64> = points.iter().map(|point| distance(&centroids[0], point)).collect();
+    while centroids.len() < k {
+        let total: f64 = closest.iter().sum();
+        if total <= 0.0 {
+            return Err("Fewer distinct hands than buckets");
+        }
+        let mut target = rng.next_f64() * total;
+        let chosen = closest
+            .iter()
+            .position(|&weight| {
+                target -= weight;
+                target < 0.0 && weight > 0.0
+            })
+            .unwrap_or_else(|| closest.iter().rposition(|&weight| weight > 0.0).unwrap());
+        centroids.push(points[chosen].clone());
+        for (point, closest) in points.iter().zip(closest.iter_mut()) {
+            *closest = closest.min(distance(&points[chosen], point));
+        }
+    }
+
+    let mut assignments = vec![usize::MAX; points.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for (point, assignment) in points.iter().zip(assignments.iter_mut()) {
+            let bucket = nearest(&centroids, point);
+            changed |= bucket != *assignment;
+            *assignment = bucket;
+        }
+        if !changed {
+            break;
+        }
+
+        // A bucket left empty keeps its centroid
+        let mut sums = vec![vec![0.0; points[0].len()]; k];
+        let mut counts = vec![0; k];
+        for (point, &bucket) in points.iter().zip(&assignments) {
+            counts[bucket] += 1;
+            for (sum, value) in sums[bucket].iter_mut().zip(point) {
+                *sum += value;
+            }
+        }
+        for ((centroid, sum), count) in centroids.iter_mut().zip(sums).zip(counts) {
+            if count > 0 {
+                *centroid = sum.into_iter().map(|sum| sum / count as f64).collect();
+            }
+        }
+    }
+    Ok(centroids)
+}
+
+// Mean equity of a histogram, taking each bin at its middle
+fn mean_equity(histogram: &[f64]) -> f64 {
+    let bins = histogram.len() as f64;
+    histogram.iter().enumerate().map(|(bin, share)| share * (bin as f64 + 0.5) / bins).sum()
+}
+
+// Hands of one street grouped into buckets of similar equity distributions,
+// bucket 0 holding the weakest. Suit-isomorphic hands share their runouts, so
+// they always land in the same bucket.
+#[derive(Debug, PartialEq, Clone)]
+pub struct EquityBuckets {
+    settings: BucketSettings,
+    seed: u64,
+    centroids: Vec<Vec<f64>>,
+}
+
+impl EquityBuckets {
+    // Clusters `training_hands` random deals, or every class of the street
+    // when there are no more of them (169 before the flop). The result only
+    // depends on the settings, the number of hands and the seed.
+    pub fn train(
+        settings: BucketSettings,
+        training_hands: usize,
+        seed: u64,
+        threads: usize,
+    ) -> Result<Self, &'static str> {
+        if threads == 0 {
+            return Err("At least one thread is needed");
+        }
+        if training_hands < settings.buckets {
+            return Err("At least one training hand per bucket is needed");
+        }
+        let mut buckets = EquityBuckets { settings, seed, centroids: Vec::new() };
+
+        let indexer = settings.indexer();
+        let every_class = indexer.size() <= training_hands as u64;
+        let jobs = if every_class { indexer.size() as usize } else { training_hands };
+        let simulation = Simulation::new(seed).with_threads(threads);
+        let points = simulation
+            .map(jobs, |job, rng| {
+                let cards = if every_class {
+                    indexer.unindex(job as u64)?
+                } else {
+                    let mut deck: Vec<Card> = (0..52).map(Card::from_index).collect();
+                    rng.shuffle(&mut deck);
+                    deck.truncate(2 + settings.board_cards);
+                    deck
+                };
+                buckets.histogram([cards[0], cards[1]], &cards[2..])
+            })
+            .into_iter()
+            .collect::<Result<Vec<_>, &'static str>>()?;
+
+        let mut centroids = kmeans(&points, settings.buckets, &mut Rng::new(seed))?;
+        centroids.sort_by(|a, b| mean_equity(a).total_cmp(&mean_equity(b)));
+        buckets.centroids = centroids;
+        Ok(buckets)
+    }
+
+    pub fn settings(&self) -> BucketSettings {
+        self.settings
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn centroid(&self, bucket: usize) -> &[f64] {
+        &self.centroids[bucket]
+    }
+
+    pub fn mean_equity(&self, bucket: usize) -> f64 {
+        mean_equity(&self.centroids[bucket])
+    }
+
+    // The equity histogram of a hand, its runouts drawn from a generator
+    // seeded by the hand's class
+    pub fn histogram(&self, hole_cards: [Card; 2], board: &[Card]) -> Result<Vec<f64>, &'static str> {
+        if board.len() != self.settings.board_cards {
+            return Err("Wrong number of board cards for these buckets");
+        }
+        let indexer = self.settings.indexer();
+        let cards: Vec<Card> = hole_cards.iter().chain(board).copied().collect();
+        let canonical = indexer.canonicalize(&cards)?;
+        let mut rng = Rng::stream(self.seed, indexer.index(&cards)?);
+        equity_histogram(
+            [canonical[0], canonical[1]],
+            &canonical[2..],
+            self.settings.bins,
+            self.settings.runouts,
+            &mut rng,
+        )
+    }
+
+    pub fn bucket(&self, hole_cards: [Card; 2], board: &[Card]) -> Result<usize, &'static str> {
+        Ok(nearest(&self.centroids, &self.histogram(hole_cards, board)?))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let settings = &self.settings;
+        let mut bytes = Vec::with_capacity(HEADER_LEN + 8 * settings.buckets * settings.bins);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.push(settings.board_cards as u8);
+        bytes.extend_from_slice(&(settings.bins as u16).to_le_bytes());
+        bytes.extend_from_slice(&settings.runouts.to_le_bytes());
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&(settings.buckets as u32).to_le_bytes());
+        for value in self.centroids.iter().flatten() {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+            return Err("Not an equity bucket file");
+        }
+        if bytes[4] != VERSION {
+            return Err("Unsupported equity bucket file version");
+        }
+        let bins = u16::from_le_bytes([bytes[6], bytes[7]]) as usize;
+        let runouts = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        let seed = u64::from_le_bytes(bytes[12..20].try_into().unwrap());
+        let buckets = u32::from_le_bytes(bytes[20..24].try_into().unwrap()) as usize;
+        let settings = BucketSettings::new(bytes[5] as usize, buckets)?.with_bins(bins)?.with_runouts(runouts)?;
+        if bytes.len() != HEADER_LEN + 8 * buckets * bins {
+            return Err("Truncated equity bucket file");
+        }
+
+        let centroids = bytes[HEADER_LEN..]
+            .chunks_exact(8 * bins)
+            .map(|centroid| {
+                centroid.chunks_exact(8).map(|value| f64::from_le_bytes(value.try_into().unwrap())).collect()
+            })
+            .collect();
+        Ok(EquityBuckets { settings, seed, centroids })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        EquityBuckets::from_bytes(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::{parse_cards, parse_hole_cards};
+    use std::sync::OnceLock;
+
+    fn hole(cards: &str) -> [Card; 2] {
+        parse_hole_cards(cards).unwrap()
+    }
+
+    fn flop_buckets() -> &'static EquityBuckets {
+        static BUCKETS: OnceLock<EquityBuckets> = OnceLock::new();
+        BUCKETS.get_or_init(|| {
+            let settings = BucketSettings::new(3, 4).unwrap().with_runouts(20).unwrap();
+            EquityBuckets::train(settings, 60, 5, 2).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_equity_histogram() {
+        let mut rng = Rng::new(1);
+        // A royal flush beats everything on the river
+        let board = parse_cards("QS JS 10S 2H 3D").unwrap();
+        let royal = equity_histogram(hole("AS KS"), &board, 10, 50, &mut rng).unwrap();
+        assert_eq!(royal[9], 1.0);
+        assert_eq!(royal.iter().sum::<f64>(), 1.0);
+
+        // All 46 rivers on the turn: the nut flush draw gets to the top with
+        // nine spades and three aces, and is mostly weak otherwise
+        let board = parse_cards("QS JS 2H 3D").unwrap();
+        let draw = equity_histogram(hole("AS 5S"), &board, 10, 46, &mut rng).unwrap();
+        assert!((draw[9] - 12.0 / 46.0).abs() < 1e-12);
+        assert!(draw[..5].iter().sum::<f64>() > 0.5);
+        assert!((draw.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        let aces = equity_histogram(hole("AS AH"), &[], 10, 20, &mut rng).unwrap();
+        let rags = equity_histogram(hole("7C 2D"), &[], 10, 20, &mut rng).unwrap();
+        assert!(mean_equity(&aces) > mean_equity(&rags) + 0.3);
+    }
+
+    #[test]
+    fn test_training_is_deterministic() {
+        let settings = BucketSettings::new(3, 4).unwrap().with_runouts(20).unwrap();
+        let several = EquityBuckets::train(settings, 60, 5, 1).unwrap();
+        assert_eq!(&several, flop_buckets());
+        assert_ne!(&EquityBuckets::train(settings, 60, 6, 2).unwrap(), flop_buckets());
+
+        let buckets = flop_buckets();
+        assert!((0..3).all(|bucket| buckets.mean_equity(bucket) < buckets.mean_equity(bucket + 1)));
+        assert!((0..4).all(|bucket| (buckets.centroid(bucket).iter().sum::<f64>() - 1.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_bucket_lookup() {
+        let buckets = flop_buckets();
+        let board = parse_cards("KH 7H 2C").unwrap();
+        let set = buckets.bucket(hole("KS KC"), &board).unwrap();
+        assert_eq!(set, 3);
+        assert!(buckets.bucket(hole("4D 3S"), &board).unwrap() < set);
+
+        // The same hand with the suits renamed
+        let renamed = parse_cards("KD 7D 2S").unwrap();
+        assert_eq!(buckets.bucket(hole("KC KH"), &renamed).unwrap(), set);
+        assert_eq!(
+            buckets.histogram(hole("QH JH"), &board).unwrap(),
+            buckets.histogram(hole("QD JD"), &renamed).unwrap()
+        );
+
+        // Before the flop every class is clustered
+        let settings = BucketSettings::new(0, 3).unwrap().with_runouts(5).unwrap();
+        let preflop = EquityBuckets::train(settings, 169, 1, 2).unwrap();
+        assert_eq!(preflop.bucket(hole("AS AH"), &[]).unwrap(), 2);
+        assert_eq!(preflop.bucket(hole("7C 2D"), &[]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let buckets = flop_buckets();
+        let bytes = buckets.to_bytes();
+        assert_eq!(bytes.len(), 24 + 8 * 4 * 10);
+        assert_eq!(&EquityBuckets::from_bytes(&bytes).unwrap(), buckets);
+
+        let path = std::env::temp_dir().join(format!("equity-buckets-test-{}.bin", std::process::id()));
+        buckets.save(&path).unwrap();
+        let loaded = EquityBuckets::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(&loaded.unwrap(), buckets);
+
+        assert!(EquityBuckets::from_bytes(&bytes[..100]).is_err());
+        assert!(EquityBuckets::from_bytes(b"not buckets").is_err());
+        let mut other_version = bytes.clone();
+        other_version[4] = 2;
+        assert!(EquityBuckets::from_bytes(&other_version).is_err());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(BucketSettings::new(2, 4).is_err());
+        assert!(BucketSettings::new(3, 0).is_err());
+        assert!(BucketSettings::new(3, 4).unwrap().with_bins(0).is_err());
+        let settings = BucketSettings::new(3, 4).unwrap();
+        assert!(EquityBuckets::train(settings, 3, 1, 1).is_err());
+        assert!(EquityBuckets::train(settings, 10, 1, 0).is_err());
+        // A single river bin can't tell two buckets apart
+        let settings = BucketSettings::new(5, 2).unwrap().with_bins(1).unwrap();
+        assert!(EquityBuckets::train(settings, 10, 1, 1).is_err());
+
+        let buckets = flop_buckets();
+        assert!(buckets.bucket(hole("AS KS"), &parse_cards("2H 3H 4H 5H").unwrap()).is_err());
+        assert!(buckets.bucket(hole("AS KS"), &parse_cards("AS 3H 4H").unwrap()).is_err());
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum Suit {
     Hearts,
     Diamonds,
@@ -6,7 +8,7 @@ pub enum Suit {
     Spades,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
 pub enum Rank {
     Two = 2,
     Three,
@@ -23,13 +25,70 @@ pub enum Rank {
     Ace,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
+}
+
+impl Rank {
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct Card {
     pub rank: Rank,
     pub suit: Suit,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+impl Card {
+    // Position of the card in a 52-card deck ordered by suit then rank
+    pub fn index(&self) -> usize {
+        self.suit as usize * 13 + (self.rank as usize - 2)
+    }
+
+    pub fn from_index(index: usize) -> Card {
+        Card {
+            rank: Rank::ALL[index % 13],
+            suit: Suit::ALL[index / 13],
+        }
+    }
+}
+
+// Same notation as `parse_card`, e.g. "10S" or "AH"
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rank = match self.rank {
+            Rank::Ten => "10".to_string(),
+            Rank::Jack => "J".to_string(),
+            Rank::Queen => "Q".to_string(),
+            Rank::King => "K".to_string(),
+            Rank::Ace => "A".to_string(),
+            rank => (rank as u8).to_string(),
+        };
+        let suit = match self.suit {
+            Suit::Hearts => 'H',
+            Suit::Diamonds => 'D',
+            Suit::Clubs => 'C',
+            Suit::Spades => 'S',
+        };
+        write!(f, "{}{}", rank, suit)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
 pub enum HandType {
     HighCard,
     OnePair,
@@ -43,6 +102,22 @@ pub enum HandType {
     RoyalFlush,
 }
 
+impl HandType {
+    // Ordered from weakest to strongest, so `HandType::ALL[t as usize] == t`
+    pub const ALL: [HandType; 10] = [
+        HandType::HighCard,
+        HandType::OnePair,
+        HandType::TwoPair,
+        HandType::ThreeOfAKind,
+        HandType::Straight,
+        HandType::Flush,
+        HandType::FullHouse,
+        HandType::FourOfAKind,
+        HandType::StraightFlush,
+        HandType::RoyalFlush,
+    ];
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,4 +150,20 @@ mod tests {
         assert!(HandType::TwoPair > HandType::OnePair);
         assert!(HandType::OnePair > HandType::HighCard);
     }
+
+    #[test]
+    fn test_card_index_round_trip() {
+        for index in 0..52 {
+            assert_eq!(Card::from_index(index).index(), index);
+        }
+        assert_eq!(Card { rank: Rank::Two, suit: Suit::Hearts }.index(), 0);
+        assert_eq!(Card { rank: Rank::Ace, suit: Suit::Spades }.index(), 51);
+    }
+
+    #[test]
+    fn test_card_display() {
+        assert_eq!(Card { rank: Rank::Ten, suit: Suit::Spades }.to_string(), "10S");
+        assert_eq!(Card { rank: Rank::Queen, suit: Suit::Hearts }.to_string(), "QH");
+        assert_eq!(Card { rank: Rank::Seven, suit: Suit::Diamonds }.to_string(), "7D");
+    }
 } 
\ No newline at end of file
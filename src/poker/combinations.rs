@@ -0,0 +1,115 @@
+// Pascal's triangle for everything a deck of cards needs, so the hot loops
+// indexing card combinations don't recompute binomials
+const TABLE_N: usize = 53;
+const TABLE_K: usize = 8;
+const BINOMIALS: [[u64; TABLE_K]; TABLE_N] = pascal_triangle();
+
+const fn pascal_triangle() -> [[u64; TABLE_K]; TABLE_N] {
+    let mut table = [[0u64; TABLE_K]; TABLE_N];
+    let mut n = 0;
+    while n < TABLE_N {
+        table[n][0] = 1;
+        let mut k = 1;
+        while k < TABLE_K && k <= n {
+            table[n][k] = table[n - 1][k - 1] + table[n - 1][k];
+            k += 1;
+        }
+        n += 1;
+    }
+    table
+}
+
+// Number of ways to choose `k` items out of `n`
+pub fn binomial(n: usize, k: usize) -> u64 {
+    if k > n {
+        return 0;
+    }
+    if n < TABLE_N && k < TABLE_K {
+        return BINOMIALS[n][k];
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1u64, |acc, i| acc * (n - i) as u64 / (i + 1) as u64)
+}
+
+// Calls `f` with every `k`-item combination of `items`, in lexicographic order
+// of positions. The slice passed to `f` is reused between calls.
+pub fn for_each_combination<T: Copy>(items: &[T], k: usize, mut f: impl FnMut(&[T])) {
+    if k > items.len() {
+        return;
+    }
+
+    let mut positions: Vec<usize> = (0..k).collect();
+    let mut chosen: Vec<T> = positions.iter().map(|&i| items[i]).collect();
+
+    loop {
+        f(&chosen);
+
+        // Find the rightmost position that can still move forward
+        let mut i = k;
+        while i > 0 && positions[i - 1] == items.len() - k + i - 1 {
+            i -= 1;
+        }
+        if i == 0 {
+            return;
+        }
+        i -= 1;
+
+        positions[i] += 1;
+        chosen[i] = items[positions[i]];
+        for j in i + 1..k {
+            positions[j] = positions[j - 1] + 1;
+            chosen[j] = items[positions[j]];
+        }
+    }
+}
+
+// Dense index of a set of distinct values given in increasing order, among all
+// sets of the same size (colexicographic rank)
+pub fn combination_index(sorted: &[usize]) -> usize {
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| binomial(value, i + 1) as usize)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binomial() {
+        assert_eq!(binomial(52, 5), 2_598_960);
+        assert_eq!(binomial(47, 0), 1);
+        assert_eq!(binomial(5, 5), 1);
+        assert_eq!(binomial(3, 5), 0);
+        assert_eq!(binomial(60, 30), 118_264_581_564_861_424);
+    }
+
+    #[test]
+    fn test_for_each_combination() {
+        let mut seen = Vec::new();
+        for_each_combination(&[1, 2, 3, 4], 2, |combo| seen.push(combo.to_vec()));
+        assert_eq!(
+            seen,
+            vec![vec![1, 2], vec![1, 3], vec![1, 4], vec![2, 3], vec![2, 4], vec![3, 4]]
+        );
+
+        let mut count = 0;
+        for_each_combination(&[0; 10], 0, |_| count += 1);
+        assert_eq!(count, 1);
+
+        count = 0;
+        for_each_combination(&(0..47).collect::<Vec<_>>(), 3, |_| count += 1);
+        assert_eq!(count, binomial(47, 3));
+    }
+
+    #[test]
+    fn test_combination_index_is_dense() {
+        let items: Vec<usize> = (0..9).collect();
+        let mut indexes = Vec::new();
+        for_each_combination(&items, 4, |combo| indexes.push(combination_index(combo)));
+        indexes.sort();
+        assert_eq!(indexes, (0..binomial(9, 4) as usize).collect::<Vec<_>>());
+    }
+}
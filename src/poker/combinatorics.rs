@@ -0,0 +1,174 @@
+use super::card::{Card, Rank, Suit};
+use super::combinations::binomial;
+use super::range::{HandClass, HandClassKind, Range};
+
+// Suits of `rank` still unseen once the known cards are out
+fn live_suits(rank: Rank, known: &[Card]) -> Vec<Suit> {
+    Suit::ALL
+        .into_iter()
+        .filter(|&suit| !known.contains(&Card { rank, suit }))
+        .collect()
+}
+
+fn unseen_cards(known: &[Card]) -> usize {
+    let mut seen = 0u64;
+    for card in known {
+        seen |= 1 << card.index();
+    }
+    52 - seen.count_ones() as usize
+}
+
+// Combos of a class left when the known cards (board, hero's hole cards,
+// folded cards...) can't be dealt: C(n, 2) for a pair with n cards of its rank
+// live, one per suit live in both ranks when suited, and every pairing of the
+// live cards of both ranks minus the suited ones when offsuit. AK with an ace
+// on the board leaves 3 x 4 = 12.
+pub fn combo_count(class: HandClass, known: &[Card]) -> usize {
+    let high = live_suits(class.high, known);
+    match class.kind {
+        HandClassKind::Pair => binomial(high.len(), 2) as usize,
+        kind => {
+            let low = live_suits(class.low, known);
+            let suited = high.iter().filter(|suit| low.contains(suit)).count();
+            if kind == HandClassKind::Suited {
+                suited
+            } else {
+                high.len() * low.len() - suited
+            }
+        }
+    }
+}
+
+// Combos of a range left when the known cards can't be dealt, counted by
+// weight
+pub fn range_combo_count(range: &Range, known: &[Card]) -> f64 {
+    range.without(known).total_weight()
+}
+
+// Chance of being dealt a class from the cards left, e.g. 6 / 1326 for aces
+// from a full deck
+pub fn deal_probability(class: HandClass, known: &[Card]) -> f64 {
+    let holdings = binomial(unseen_cards(known), 2);
+    if holdings == 0 {
+        return 0.0;
+    }
+    combo_count(class, known) as f64 / holdings as f64
+}
+
+// Chance that a random holding from the cards left is in the range, weights
+// counting as partial combos
+pub fn range_deal_probability(range: &Range, known: &[Card]) -> f64 {
+    let holdings = binomial(unseen_cards(known), 2);
+    if holdings == 0 {
+        return 0.0;
+    }
+    range_combo_count(range, known) / holdings as f64
+}
+
+// How the hero's hole cards thin out an opponent's range on a board
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BlockerEffect {
+    // Weighted combos left once the board is known
+    pub unblocked: f64,
+    // Weighted combos left once the hole cards are known too
+    pub remaining: f64,
+}
+
+impl BlockerEffect {
+    pub fn blocked(&self) -> f64 {
+        self.unblocked - self.remaining
+    }
+
+    // Share of the range the hole cards remove, 0 for an empty range
+    pub fn blocked_share(&self) -> f64 {
+        if self.unblocked > 0.0 {
+            self.blocked() / self.unblocked
+        } else {
+            0.0
+        }
+    }
+}
+
+pub fn blocker_effect(range: &Range, hole_cards: [Card; 2], board: &[Card]) -> Result<BlockerEffect, &'static str> {
+    let known: Vec<Card> = hole_cards.iter().chain(board).copied().collect();
+    if unseen_cards(&known) != 52 - known.len() {
+        return Err("Duplicate card");
+    }
+    Ok(BlockerEffect {
+        unblocked: range_combo_count(range, board),
+        remaining: range_combo_count(range, &known),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::{parse_cards, parse_hole_cards};
+    use super::super::range::parse_range;
+
+    fn class(text: &str) -> HandClass {
+        HandClass::parse(text).unwrap()
+    }
+
+    fn cards(text: &str) -> Vec<Card> {
+        parse_cards(text).unwrap()
+    }
+
+    #[test]
+    fn test_combo_count() {
+        assert_eq!(combo_count(class("AA"), &[]), 6);
+        assert_eq!(combo_count(class("AKs"), &[]), 4);
+        assert_eq!(combo_count(class("AKo"), &[]), 12);
+
+        // An ace on the board
+        let board = cards("AS 7H 2C");
+        assert_eq!(combo_count(class("AKs"), &board) + combo_count(class("AKo"), &board), 12);
+        assert_eq!(combo_count(class("AKs"), &board), 3);
+        assert_eq!(combo_count(class("AA"), &board), 3);
+        assert_eq!(combo_count(class("77"), &board), 3);
+
+        // Every class agrees with the combos that avoid the known cards
+        let known = cards("AS KS 7H 7D 2C");
+        for class in HandClass::all() {
+            let expected = class.combos().iter().filter(|combo| !combo.iter().any(|card| known.contains(card))).count();
+            assert_eq!(combo_count(class, &known), expected, "{}", class);
+        }
+    }
+
+    #[test]
+    fn test_deal_probability() {
+        assert!((deal_probability(class("AA"), &[]) - 6.0 / 1326.0).abs() < 1e-15);
+        assert!((deal_probability(class("AKs"), &[]) - 4.0 / 1326.0).abs() < 1e-15);
+        let total: f64 = HandClass::all().iter().map(|&class| deal_probability(class, &[])).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+
+        // Holding a king, the other player has KK 3 times in 1225
+        let hole = cards("KS QD");
+        assert!((deal_probability(class("KK"), &hole) - 3.0 / 1225.0).abs() < 1e-15);
+
+        let range = parse_range("QQ+, AK").unwrap();
+        assert!((range_deal_probability(&range, &[]) - 34.0 / 1326.0).abs() < 1e-15);
+        let all: Vec<Card> = (0..52).map(Card::from_index).collect();
+        assert_eq!(deal_probability(class("AA"), &all), 0.0);
+    }
+
+    #[test]
+    fn test_blocker_effect() {
+        let range = parse_range("AA, KK, AK").unwrap();
+        let board = cards("QH 7H 2C");
+
+        // The ace of spades removes half the aces and a quarter of AK
+        let effect = blocker_effect(&range, parse_hole_cards("AS 5D").unwrap(), &board).unwrap();
+        assert_eq!(effect.unblocked, 28.0);
+        assert_eq!(effect.remaining, 3.0 + 6.0 + 12.0);
+        assert_eq!(effect.blocked(), 7.0);
+        assert!((effect.blocked_share() - 0.25).abs() < 1e-15);
+
+        let weighted = parse_range("AA:0.5").unwrap();
+        let effect = blocker_effect(&weighted, parse_hole_cards("AS AD").unwrap(), &board).unwrap();
+        assert_eq!((effect.unblocked, effect.remaining), (3.0, 0.5));
+
+        assert!(blocker_effect(&range, parse_hole_cards("QH 5D").unwrap(), &board).is_err());
+        assert_eq!(blocker_effect(&Range::new(), parse_hole_cards("AS 5D").unwrap(), &board).unwrap().blocked_share(), 0.0);
+    }
+}
@@ -0,0 +1,137 @@
+use super::card::{Card, Rank, Suit};
+use super::hand::Hand;
+use super::rng::Rng;
+
+// A deck of cards, dealt from the end of `cards` (the top of the deck)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Deck {
+    cards: Vec<Card>,
+}
+
+impl Deck {
+    // Full 52-card deck in a fixed order
+    pub fn new() -> Self {
+        let cards = Suit::ALL
+            .iter()
+            .flat_map(|&suit| Rank::ALL.iter().map(move |&rank| Card { rank, suit }))
+            .collect();
+        Deck { cards }
+    }
+
+    pub fn shuffled(seed: u64) -> Self {
+        let mut deck = Deck::new();
+        deck.shuffle(&mut Rng::new(seed));
+        deck
+    }
+
+    pub fn shuffle(&mut self, rng: &mut Rng) {
+        rng.shuffle(&mut self.cards);
+    }
+
+    pub fn deal(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    pub fn deal_many(&mut self, count: usize) -> Result<Vec<Card>, &'static str> {
+        if count > self.cards.len() {
+            return Err("Not enough cards left in the deck");
+        }
+        let mut dealt = self.cards.split_off(self.cards.len() - count);
+        dealt.reverse();
+        Ok(dealt)
+    }
+
+    pub fn deal_hand(&mut self) -> Result<Hand, &'static str> {
+        Hand::new(self.deal_many(5)?)
+    }
+
+    // Discards the top card
+    pub fn burn(&mut self) -> Result<Card, &'static str> {
+        self.deal().ok_or("Not enough cards left in the deck")
+    }
+
+    // Takes known cards (hands already dealt, dead cards...) out of the deck
+    pub fn remove(&mut self, cards: &[Card]) -> Result<(), &'static str> {
+        for card in cards {
+            match self.cards.iter().position(|c| c == card) {
+                Some(position) => {
+                    self.cards.remove(position);
+                }
+                None => return Err("Card is not in the deck"),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.cards.contains(card)
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Deck::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::parse_cards;
+
+    #[test]
+    fn test_new_deck_has_every_card_once() {
+        let deck = Deck::new();
+        assert_eq!(deck.remaining(), 52);
+        for index in 0..52 {
+            assert!(deck.contains(&Card::from_index(index)));
+        }
+    }
+
+    #[test]
+    fn test_deal_and_burn() {
+        let mut deck = Deck::shuffled(1);
+        let top = deck.cards()[51];
+
+        assert_eq!(deck.burn().unwrap(), top);
+        let hand = deck.deal_hand().unwrap();
+        assert_eq!(hand.cards.len(), 5);
+        assert_eq!(deck.remaining(), 46);
+        assert!(!hand.cards.iter().any(|card| deck.contains(card) || *card == top));
+
+        assert!(deck.deal_many(47).is_err());
+        assert_eq!(deck.deal_many(46).unwrap().len(), 46);
+        assert_eq!(deck.deal(), None);
+        assert!(deck.burn().is_err());
+    }
+
+    #[test]
+    fn test_remove_known_cards() {
+        let mut deck = Deck::new();
+        let known = parse_cards("AS KD 2C").unwrap();
+
+        deck.remove(&known).unwrap();
+        assert_eq!(deck.remaining(), 49);
+        assert!(!known.iter().any(|card| deck.contains(card)));
+        assert!(deck.remove(&known[..1]).is_err());
+    }
+
+    #[test]
+    fn test_seeded_shuffle_is_reproducible() {
+        assert_eq!(Deck::shuffled(42), Deck::shuffled(42));
+        assert_ne!(Deck::shuffled(42), Deck::shuffled(43));
+        assert_ne!(Deck::shuffled(42), Deck::new());
+
+        let mut dealt = Deck::shuffled(42).deal_many(52).unwrap();
+        dealt.sort_by_key(|card| card.index());
+        assert_eq!(dealt, Deck::new().cards());
+    }
+}
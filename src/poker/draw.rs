@@ -0,0 +1,181 @@
+use super::card::{Card, HandType};
+use super::deck::Deck;
+use super::hand::Hand;
+use super::hold::hold_outcomes;
+use super::value::HandValue;
+use super::video_poker::Paytable;
+
+// One of the 32 ways to discard from a five-card draw hand
+#[derive(Debug, PartialEq, Clone)]
+pub struct DiscardOption {
+    pub discard: [bool; 5],
+    pub kept: Vec<Card>,
+    // Probability of ending with each hand type, indexed like `HandType::ALL`
+    pub distribution: [f64; 10],
+    // Average payout of the final hand under the paytable used
+    pub expected_value: f64,
+    // Probability of ending strictly better than the opponent's hand, if any
+    pub beat_probability: Option<f64>,
+}
+
+impl DiscardOption {
+    pub fn probability(&self, hand_type: HandType) -> f64 {
+        self.distribution[hand_type as usize]
+    }
+}
+
+// Final hand types of a discard, what they pay and how many of them beat the
+// opponent
+#[derive(Default)]
+struct DrawTally {
+    counts: [u64; 10],
+    pay: u64,
+    beats: u64,
+}
+
+// Exact outcome of every discard, computed by drawing every possible
+// replacement from the unseen cards. The opponent's hand, when known, is
+// unseen too. Sorted from best to worst: by chance of beating the opponent
+// when there is one, by expected value otherwise.
+pub fn discard_options(
+    hand: &Hand,
+    paytable: &Paytable,
+    opponent: Option<&Hand>,
+) -> Result<Vec<DiscardOption>, &'static str> {
+    let dealt = &hand.cards;
+    if dealt.len() != 5 {
+        return Err("A hand must contain exactly 5 cards");
+    }
+
+    let mut deck = Deck::new();
+    deck.remove(dealt).map_err(|_| "Duplicate card")?;
+    if let Some(opponent) = opponent {
+        deck.remove(&opponent.cards).map_err(|_| "Duplicate card")?;
+    }
+    let to_beat = opponent.map(|opponent| opponent.value());
+
+    let mut options: Vec<DiscardOption> = hold_outcomes(dealt, deck.cards(), |tally: &mut DrawTally, final_hand| {
+        let value = HandValue::from_cards(final_hand);
+        tally.counts[value.hand_type() as usize] += 1;
+        tally.pay += paytable.payout(final_hand) as u64;
+        if to_beat.is_some_and(|to_beat| value > to_beat) {
+            tally.beats += 1;
+        }
+    })
+    .into_iter()
+    .map(|outcome| {
+        let draws = outcome.draws as f64;
+        DiscardOption {
+            discard: outcome.hold.map(|held| !held),
+            kept: outcome.held,
+            distribution: outcome.total.counts.map(|count| count as f64 / draws),
+            expected_value: outcome.total.pay as f64 / draws,
+            beat_probability: to_beat.map(|_| outcome.total.beats as f64 / draws),
+        }
+    })
+    .collect();
+
+    options.sort_by(|a, b| {
+        let beat = b.beat_probability.unwrap_or(0.0).total_cmp(&a.beat_probability.unwrap_or(0.0));
+        beat.then(b.expected_value.total_cmp(&a.expected_value))
+    });
+    Ok(options)
+}
+
+pub fn recommended_discard(
+    hand: &Hand,
+    paytable: &Paytable,
+    opponent: Option<&Hand>,
+) -> Result<DiscardOption, &'static str> {
+    Ok(discard_options(hand, paytable, opponent)?.remove(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::hold::hold_options;
+    use super::super::parser::parse_hand;
+
+    #[test]
+    fn test_every_discard_is_covered() {
+        let hand = parse_hand("2H 5H 9H KH 7C").unwrap();
+        let options = discard_options(&hand, &Paytable::jacks_or_better(), None).unwrap();
+
+        assert_eq!(options.len(), 32);
+        assert!(options.windows(2).all(|pair| pair[0].expected_value >= pair[1].expected_value));
+        for option in &options {
+            assert!((option.distribution.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+            assert_eq!(option.beat_probability, None);
+        }
+
+        let pat = options.iter().find(|option| option.discard == [false; 5]).unwrap();
+        assert_eq!(pat.probability(HandType::HighCard), 1.0);
+        assert_eq!(pat.expected_value, 0.0);
+    }
+
+    #[test]
+    fn test_flush_draw() {
+        let hand = parse_hand("2H 5H 9H KH 7C").unwrap();
+        let best = recommended_discard(&hand, &Paytable::jacks_or_better(), None).unwrap();
+
+        // 9 hearts make the flush, 3 kings a paying pair
+        assert_eq!(best.discard, [false, false, false, false, true]);
+        assert!((best.probability(HandType::Flush) - 9.0 / 47.0).abs() < 1e-12);
+        assert!((best.probability(HandType::OnePair) - 12.0 / 47.0).abs() < 1e-12);
+        assert!((best.expected_value - (9.0 * 6.0 + 3.0) / 47.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_matches_video_poker_holds() {
+        // Without an opponent, the same draws as video poker: four to a royal
+        // over the made flush, and a low pair over a lone high card
+        let paytable = Paytable::jacks_or_better();
+        for dealt in ["AH KH QH JH 3H", "5S 5D 9C KH 2D", "JC 8D 6S 4H 2C"] {
+            let hand = parse_hand(dealt).unwrap();
+            let holds = hold_options(&hand.cards, &paytable).unwrap();
+            let options = discard_options(&hand, &paytable, None).unwrap();
+            assert_eq!(options[0].discard, holds[0].hold.map(|held| !held));
+            for option in &options {
+                let hold = holds.iter().find(|hold| hold.hold == option.discard.map(|discarded| !discarded)).unwrap();
+                assert!((option.expected_value - hold.expected_value).abs() < 1e-12);
+            }
+        }
+
+        let best = recommended_discard(&parse_hand("AH KH QH JH 3H").unwrap(), &paytable, None).unwrap();
+        assert!((best.expected_value - (800.0 + 7.0 * 6.0 + 3.0 * 4.0 + 12.0) / 47.0).abs() < 1e-12);
+        // A low pair is worth 0.8237 coins under 9/6 Jacks or Better
+        let best = recommended_discard(&parse_hand("5S 5D 9C KH 2D").unwrap(), &paytable, None).unwrap();
+        assert_eq!(best.discard, [false, false, true, true, true]);
+        assert!((best.expected_value - 0.8237).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_against_an_opponent() {
+        // Only the flush beats the aces; the opponent holds no heart
+        let hand = parse_hand("2H 5H 9H KH 7C").unwrap();
+        let opponent = parse_hand("AS AD 3C 4C 8S").unwrap();
+        let options = discard_options(&hand, &Paytable::jacks_or_better(), Some(&opponent)).unwrap();
+
+        let best = &options[0];
+        assert_eq!(best.discard, [false, false, false, false, true]);
+        assert!((best.beat_probability.unwrap() - 9.0 / 42.0).abs() < 1e-12);
+        assert!(options.windows(2).all(|pair| pair[0].beat_probability >= pair[1].beat_probability));
+
+        // Standing pat never wins
+        let pat = options.iter().find(|option| option.discard == [false; 5]).unwrap();
+        assert_eq!(pat.beat_probability, Some(0.0));
+    }
+
+    #[test]
+    fn test_pat_royal_flush() {
+        let paytable = Paytable::jacks_or_better();
+        let hand = parse_hand("AS KS QS JS 10S").unwrap();
+        let best = recommended_discard(&hand, &paytable, None).unwrap();
+        assert_eq!(best.discard, [false; 5]);
+        assert_eq!(best.probability(HandType::RoyalFlush), 1.0);
+        assert_eq!(best.expected_value, 800.0);
+
+        let opponent = parse_hand("AH KS 2C 3D 4S").unwrap();
+        assert!(discard_options(&hand, &paytable, Some(&opponent)).is_err());
+    }
+}
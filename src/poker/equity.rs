@@ -0,0 +1,350 @@
+use super::card::Card;
+use super::combinations::{binomial, for_each_combination};
+use super::deck::Deck;
+use super::simulation::{Merge, Simulation};
+use super::value::HandValue;
+
+// Showdown results of one player over every trial (or board) looked at
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct PlayerEquity {
+    pub wins: u64,
+    pub ties: u64,
+    pub losses: u64,
+    // Share of the pot won on average, split pots counting fractionally
+    pub equity: f64,
+    // Standard error of `equity`, zero for exact results
+    pub std_error: f64,
+}
+
+impl PlayerEquity {
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.total() as f64
+    }
+
+    pub fn tie_rate(&self) -> f64 {
+        self.ties as f64 / self.total() as f64
+    }
+
+    pub fn loss_rate(&self) -> f64 {
+        self.losses as f64 / self.total() as f64
+    }
+
+    fn total(&self) -> u64 {
+        (self.wins + self.ties + self.losses).max(1)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct EquityResult {
+    // Number of boards the results are based on
+    pub trials: u64,
+    pub players: Vec<PlayerEquity>,
+}
+
+// Running totals while boards are being dealt
+#[derive(Debug, Clone)]
+pub(crate) struct Tally {
+    trials: u64,
+    wins: Vec<u64>,
+    ties: Vec<u64>,
+    losses: Vec<u64>,
+    shares: Vec<f64>,
+    squared_shares: Vec<f64>,
+}
+
+impl Tally {
+    pub(crate) fn new(players: usize) -> Self {
+        Tally {
+            trials: 0,
+            wins: vec![0; players],
+            ties: vec![0; players],
+            losses: vec![0; players],
+            shares: vec![0.0; players],
+            squared_shares: vec![0.0; players],
+        }
+    }
+
+    // Records one showdown between the players' hand values
+    pub(crate) fn record(&mut self, values: &[HandValue]) {
+        let best = values.iter().max().copied();
+        let winners = values.iter().filter(|&&value| Some(value) == best).count();
+        let share = 1.0 / winners as f64;
+
+        self.trials += 1;
+        for (player, value) in values.iter().enumerate() {
+            if Some(*value) != best {
+                self.losses[player] += 1;
+                continue;
+            }
+            if winners == 1 {
+                self.wins[player] += 1;
+            } else {
+                self.ties[player] += 1;
+            }
+            self.shares[player] += share;
+            self.squared_shares[player] += share * share;
+        }
+    }
+
+    pub(crate) fn result(&self, exact: bool) -> EquityResult {
+        let n = self.trials.max(1) as f64;
+        let players = (0..self.wins.len())
+            .map(|player| {
+                let equity = self.shares[player] / n;
+                let variance = (self.squared_shares[player] / n - equity * equity).max(0.0);
+                PlayerEquity {
+                    wins: self.wins[player],
+                    ties: self.ties[player],
+                    losses: self.losses[player],
+                    equity,
+                    std_error: if exact { 0.0 } else { (variance / n).sqrt() },
+                }
+            })
+            .collect();
+
+        EquityResult { trials: self.trials, players }
+    }
+}
+
+impl Merge for Tally {
+    fn merge(&mut self, other: Self) {
+        self.trials += other.trials;
+        for player in 0..self.wins.len() {
+            self.wins[player] += other.wins[player];
+            self.ties[player] += other.ties[player];
+            self.losses[player] += other.losses[player];
+            self.shares[player] += other.shares[player];
+            self.squared_shares[player] += other.squared_shares[player];
+        }
+    }
+}
+
+// Cards left to deal from once holdings, board and dead cards are known
+pub(crate) fn remaining_cards(
+    holdings: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<Vec<Card>, &'static str> {
+    if holdings.len() < 2 {
+        return Err("At least two players are needed");
+    }
+    if board.len() > 5 {
+        return Err("A board has at most 5 cards");
+    }
+
+    let mut deck = Deck::new();
+    for holding in holdings {
+        deck.remove(holding).map_err(|_| "Duplicate card")?;
+    }
+    deck.remove(board).map_err(|_| "Duplicate card")?;
+    deck.remove(dead).map_err(|_| "Duplicate card")?;
+    Ok(deck.cards().to_vec())
+}
+
+// Best hand of every player on a complete five-card board
+pub(crate) fn showdown_values(holdings: &[[Card; 2]], board: &[Card], values: &mut Vec<HandValue>) {
+    let mut cards = [holdings[0][0]; 7];
+    cards[2..].copy_from_slice(board);
+
+    values.clear();
+    for holding in holdings {
+        cards[..2].copy_from_slice(holding);
+        values.push(HandValue::from_cards(&cards));
+    }
+}
+
+// Estimates Hold'em equity by dealing `iterations` random runouts of the
+// board, spread over every core; the result only depends on the seed
+pub fn monte_carlo_equity(
+    holdings: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+    iterations: u64,
+    seed: u64,
+) -> Result<EquityResult, &'static str> {
+    if iterations == 0 {
+        return Err("At least one iteration is needed");
+    }
+
+    let remaining = remaining_cards(holdings, board, dead)?;
+    let missing = 5 - board.len();
+    if remaining.len() < missing {
+        return Err("Not enough cards left to complete the board");
+    }
+
+    let tally = Simulation::new(seed).run(iterations, Tally::new(holdings.len()), |trials, rng| {
+        let mut remaining = remaining.clone();
+        let mut tally = Tally::new(holdings.len());
+        let mut full_board = board.to_vec();
+        let mut values = Vec::with_capacity(holdings.len());
+
+        for _ in 0..trials {
+            // Partial shuffle: only the cards that complete the board are drawn
+            for i in 0..missing {
+                let j = i + rng.below(remaining.len() - i);
+                remaining.swap(i, j);
+            }
+            full_board.truncate(board.len());
+            full_board.extend_from_slice(&remaining[..missing]);
+
+            showdown_values(holdings, &full_board, &mut values);
+            tally.record(&values);
+        }
+        tally
+    });
+
+    Ok(tally.result(false))
+}
+
+// Exact Hold'em equity over every possible runout of the board
+pub fn exact_equity(
+    holdings: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<EquityResult, &'static str> {
+    exact_equity_with_progress(holdings, board, dead, |_, _| {})
+}
+
+// Same as `exact_equity`, calling `progress(boards_done, boards_total)`
+// regularly; pre-flop heads-up enumerations look at 1,712,304 boards
+pub fn exact_equity_with_progress(
+    holdings: &[[Card; 2]],
+    board: &[Card],
+    dead: &[Card],
+    mut progress: impl FnMut(u64, u64),
+) -> Result<EquityResult, &'static str> {
+    const PROGRESS_INTERVAL: u64 = 1 << 16;
+
+    let remaining = remaining_cards(holdings, board, dead)?;
+    let missing = 5 - board.len();
+    let total = binomial(remaining.len(), missing);
+    if total == 0 {
+        return Err("Not enough cards left to complete the board");
+    }
+
+    let mut tally = Tally::new(holdings.len());
+    let mut full_board = board.to_vec();
+    let mut values = Vec::with_capacity(holdings.len());
+    let mut done = 0;
+
+    for_each_combination(&remaining, missing, |runout| {
+        full_board.truncate(board.len());
+        full_board.extend_from_slice(runout);
+
+        showdown_values(holdings, &full_board, &mut values);
+        tally.record(&values);
+
+        done += 1;
+        if done % PROGRESS_INTERVAL == 0 {
+            progress(done, total);
+        }
+    });
+    progress(done, total);
+
+    Ok(tally.result(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::{parse_cards, parse_hole_cards};
+
+    fn holdings(hands: &[&str]) -> Vec<[Card; 2]> {
+        hands.iter().map(|hand| parse_hole_cards(hand).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_aces_against_kings() {
+        let result = monte_carlo_equity(&holdings(&["AS AH", "KS KH"]), &[], &[], 20_000, 1).unwrap();
+        let aces = result.players[0];
+        let kings = result.players[1];
+
+        assert_eq!(result.trials, 20_000);
+        assert!((aces.equity - 0.8264).abs() < 4.0 * aces.std_error);
+        assert!((aces.equity + kings.equity - 1.0).abs() < 1e-9);
+        assert_eq!(aces.wins + aces.ties + aces.losses, 20_000);
+        assert!(aces.std_error > 0.0 && aces.std_error < 0.01);
+    }
+
+    #[test]
+    fn test_complete_board_is_decided() {
+        let board = parse_cards("2C 7D 9H JS KD").unwrap();
+        let result = monte_carlo_equity(&holdings(&["AS AH", "KS KH", "QS QH"]), &board, &[], 100, 1).unwrap();
+
+        assert_eq!(result.players[1].win_rate(), 1.0);
+        assert_eq!(result.players[0].loss_rate(), 1.0);
+        assert_eq!(result.players[1].std_error, 0.0);
+    }
+
+    #[test]
+    fn test_split_pot() {
+        let board = parse_cards("2C 3D 4H 5S 6C").unwrap();
+        let result = monte_carlo_equity(&holdings(&["AS AH", "KS KH"]), &board, &[], 10, 1).unwrap();
+
+        assert_eq!(result.players[0].tie_rate(), 1.0);
+        assert_eq!(result.players[0].equity, 0.5);
+    }
+
+    #[test]
+    fn test_seed_reproducibility() {
+        let hands = holdings(&["AS KS", "QH QD"]);
+        let dead = parse_cards("2S").unwrap();
+        let first = monte_carlo_equity(&hands, &[], &dead, 2_000, 9).unwrap();
+        let second = monte_carlo_equity(&hands, &[], &dead, 2_000, 9).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_exact_equity_on_the_turn() {
+        // Kings need one of the two remaining kings among 44 rivers
+        let board = parse_cards("2C 7D 9H 3S").unwrap();
+        let result = exact_equity(&holdings(&["AS AH", "KS KH"]), &board, &[]).unwrap();
+
+        assert_eq!(result.trials, 44);
+        assert_eq!(result.players[1].wins, 2);
+        assert_eq!(result.players[0].wins, 42);
+        assert_eq!(result.players[0].std_error, 0.0);
+    }
+
+    #[test]
+    fn test_exact_equity_pre_flop() {
+        let mut calls = 0;
+        let mut last = (0, 0);
+        let result = exact_equity_with_progress(&holdings(&["AS AH", "KS KH"]), &[], &[], |done, total| {
+            calls += 1;
+            last = (done, total);
+        })
+        .unwrap();
+
+        assert_eq!(result.trials, 1_712_304);
+        assert_eq!(last, (1_712_304, 1_712_304));
+        assert!(calls > 20);
+
+        let aces = result.players[0];
+        assert_eq!(aces.wins + aces.ties + aces.losses, 1_712_304);
+        assert!((aces.equity - 0.8264).abs() < 0.001);
+
+        // Monte Carlo agrees within its error
+        let estimate = monte_carlo_equity(&holdings(&["AS AH", "KS KH"]), &[], &[], 20_000, 5).unwrap();
+        assert!((estimate.players[0].equity - aces.equity).abs() < 4.0 * estimate.players[0].std_error);
+    }
+
+    #[test]
+    fn test_exact_equity_dead_cards() {
+        let board = parse_cards("2C 7D 9H 3S").unwrap();
+        let dead = parse_cards("KC").unwrap();
+        let result = exact_equity(&holdings(&["AS AH", "KS KH"]), &board, &dead).unwrap();
+
+        assert_eq!(result.trials, 43);
+        assert_eq!(result.players[1].wins, 1);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let board = parse_cards("AS 2C 3D").unwrap();
+        assert!(monte_carlo_equity(&holdings(&["AS KS"]), &[], &[], 10, 1).is_err());
+        assert!(monte_carlo_equity(&holdings(&["AS KS", "AS QH"]), &[], &[], 10, 1).is_err());
+        assert!(monte_carlo_equity(&holdings(&["AS KS", "QH QD"]), &board, &[], 10, 1).is_err());
+        assert!(monte_carlo_equity(&holdings(&["AH KS", "QH QD"]), &[], &[], 0, 1).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use super::card::Card;
+use super::combinations::for_each_combination;
+use super::deck::Deck;
+use super::range::Range;
+use super::value::HandValue;
+use std::cmp::Ordering;
+
+// Rows and columns of the potential table
+const AHEAD: usize = 0;
+const TIED: usize = 1;
+const BEHIND: usize = 2;
+
+// Strength of a holding on the flop, turn or river against one opponent, as
+// fractions between 0 and 1
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct HandStrength {
+    // HS: share of opponent holdings beaten right now, ties counting half
+    pub hand_strength: f64,
+    // PPot: chance of getting ahead by the river when behind, ties counting half
+    pub positive_potential: f64,
+    // NPot: chance of falling behind by the river when ahead, ties counting half
+    pub negative_potential: f64,
+    // EHS = HS x (1 - NPot) + (1 - HS) x PPot
+    pub effective_strength: f64,
+}
+
+fn compare(hero: HandValue, villain: HandValue) -> usize {
+    match hero.cmp(&villain) {
+        Ordering::Greater => AHEAD,
+        Ordering::Equal => TIED,
+        Ordering::Less => BEHIND,
+    }
+}
+
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator > 0.0 {
+        numerator / denominator
+    } else {
+        0.0
+    }
+}
+
+// Enumerates every opponent holding and every board completion to the river.
+// Without a range all holdings weigh the same; with one, each holding weighs
+// what the range gives it once the known cards are removed.
+pub fn hand_strength(
+    hole_cards: [Card; 2],
+    board: &[Card],
+    range: Option<&Range>,
+) -> Result<HandStrength, &'static str> {
+    if board.len() < 3 || board.len() > 5 {
+        return Err("A board has 3 to 5 cards");
+    }
+    let mut deck = Deck::new();
+    deck.remove(&hole_cards).map_err(|_| "Duplicate card")?;
+    deck.remove(board).map_err(|_| "Duplicate card")?;
+    let unseen = deck.cards();
+
+    let opponents: Vec<([Card; 2], f64)> = match range {
+        Some(range) => {
+            let known: Vec<Card> = hole_cards.iter().chain(board).copied().collect();
+            range.without(&known).combos().into_iter().filter(|&(_, weight)| weight > 0.0).collect()
+        }
+        None => {
+            let mut combos = Vec::new();
+            for_each_combination(unseen, 2, |pair| combos.push(([pair[0], pair[1]], 1.0)));
+            combos
+        }
+    };
+    if opponents.is_empty() {
+        return Err("The opponent range has no combo left once the known cards are removed");
+    }
+
+    // The hero's hand after each runout doesn't depend on the opponent
+    let mut cards = [hole_cards[0]; 7];
+    cards[..2].copy_from_slice(&hole_cards);
+    cards[2..2 + board.len()].copy_from_slice(board);
+    let hero_now = HandValue::from_cards(&cards[..2 + board.len()]);
+    let mut runouts: Vec<(Vec<Card>, HandValue)> = Vec::new();
+    for_each_combination(unseen, 5 - board.len(), |runout| {
+        cards[2 + board.len()..].copy_from_slice(runout);
+        runouts.push((runout.to_vec(), HandValue::from_cards(&cards)));
+    });
+
+    // Weight of each (now, at the river) pair of states
+    let mut now = [0.0; 3];
+    let mut potential = [[0.0; 3]; 3];
+    for (holding, weight) in opponents {
+        cards[..2].copy_from_slice(&holding);
+        let state = compare(hero_now, HandValue::from_cards(&cards[..2 + board.len()]));
+        now[state] += weight;
+
+        for (runout, hero_river) in &runouts {
+            if runout.iter().any(|card| holding.contains(card)) {
+                continue;
+            }
+            cards[2 + board.len()..].copy_from_slice(runout);
+            potential[state][compare(*hero_river, HandValue::from_cards(&cards))] += weight;
+        }
+    }
+
+    let total: f64 = now.iter().sum();
+    let hand_strength = (now[AHEAD] + now[TIED] / 2.0) / total;
+
+    let row_total = |state: usize| potential[state].iter().sum::<f64>();
+    let positive_potential = ratio(
+        potential[BEHIND][AHEAD] + potential[BEHIND][TIED] / 2.0 + potential[TIED][AHEAD] / 2.0,
+        row_total(BEHIND) + row_total(TIED) / 2.0,
+    );
+    let negative_potential = ratio(
+        potential[AHEAD][BEHIND] + potential[TIED][BEHIND] / 2.0 + potential[AHEAD][TIED] / 2.0,
+        row_total(AHEAD) + row_total(TIED) / 2.0,
+    );
+
+    Ok(HandStrength {
+        hand_strength,
+        positive_potential,
+        negative_potential,
+        effective_strength: hand_strength * (1.0 - negative_potential)
+            + (1.0 - hand_strength) * positive_potential,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::{parse_cards, parse_hole_cards};
+    use super::super::range::parse_range;
+
+    fn strength(hole_cards: &str, board: &str, range: Option<&str>) -> HandStrength {
+        let range = range.map(|range| parse_range(range).unwrap());
+        hand_strength(parse_hole_cards(hole_cards).unwrap(), &parse_cards(board).unwrap(), range.as_ref())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_flop_example() {
+        // The usual example from the literature: ace-queen, no pair on a
+        // two-heart flop
+        let result = strength("AD QC", "3H 4C JH", None);
+        assert!((result.hand_strength - 0.585).abs() < 0.001);
+        assert!((result.positive_potential - 0.208).abs() < 0.001);
+        assert!((result.negative_potential - 0.274).abs() < 0.001);
+        let ehs = result.hand_strength * (1.0 - result.negative_potential)
+            + (1.0 - result.hand_strength) * result.positive_potential;
+        assert!((result.effective_strength - ehs).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_draws_and_made_hands() {
+        // A flush draw is weak now but has plenty of potential
+        let draw = strength("AH 2H", "KH 7H 9C 4S", None);
+        assert!(draw.positive_potential > 0.2);
+        assert!(draw.effective_strength > draw.hand_strength);
+
+        // A royal flush on the river: nothing left to come
+        let nuts = strength("AS KS", "QS JS 10S 2D 3C", None);
+        assert_eq!(nuts.hand_strength, 1.0);
+        assert_eq!(nuts.positive_potential, 0.0);
+        assert_eq!(nuts.negative_potential, 0.0);
+        assert_eq!(nuts.effective_strength, 1.0);
+
+        // Ties count half: 9 other queen-ten combos make the same straight
+        let straight = strength("QC 10C", "2C 7D 9H JS KD", None);
+        assert!((straight.hand_strength - (1.0 - 9.0 / 990.0 / 2.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_opponent_range() {
+        // Top pair is ahead of a random hand but not of a tight range
+        let random = strength("KS QD", "KH 7C 2D", None);
+        let tight = strength("KS QD", "KH 7C 2D", Some("QQ+, AK"));
+        assert!(random.hand_strength > 0.85);
+        assert!(tight.hand_strength < 0.3);
+
+        // Weights count: half of the beaten combos weigh half as much
+        let weighted = strength("KS QD", "KH 7C 2D", Some("AA, QQ:0.5"));
+        let aces = 6.0;
+        let queens = 3.0 * 0.5;
+        assert!((weighted.hand_strength - queens / (aces + queens)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let hole_cards = parse_hole_cards("AS KS").unwrap();
+        assert!(hand_strength(hole_cards, &parse_cards("2C 3D").unwrap(), None).is_err());
+        assert!(hand_strength(hole_cards, &parse_cards("AS 3D 4C").unwrap(), None).is_err());
+        let range = parse_range("AA").unwrap();
+        assert!(hand_strength(hole_cards, &parse_cards("AH AD 4C").unwrap(), Some(&range)).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+use super::card::Card;
+use super::combinations::{binomial, combination_index, for_each_combination};
+use super::deck::Deck;
+use super::video_poker::Paytable;
+use std::collections::HashMap;
+
+// One of the 32 ways to play a dealt video poker hand
+#[derive(Debug, PartialEq, Clone)]
+pub struct HoldOption {
+    pub hold: [bool; 5],
+    pub held: Vec<Card>,
+    // Average credits returned per coin bet
+    pub expected_value: f64,
+}
+
+// A hold and what its final hands add up to
+pub(crate) struct HoldOutcome<T> {
+    pub hold: [bool; 5],
+    pub held: Vec<Card>,
+    // Number of equally likely draws
+    pub draws: u64,
+    pub total: T,
+}
+
+// Goes through the 32 holds of a five-card hand and, for each one, every
+// final hand made by replacing the discards with cards from `unseen`, folding
+// each final hand into the hold's total with `add`
+pub(crate) fn hold_outcomes<T: Default>(
+    dealt: &[Card],
+    unseen: &[Card],
+    mut add: impl FnMut(&mut T, &[Card]),
+) -> Vec<HoldOutcome<T>> {
+    (0..32u32)
+        .map(|mask| {
+            let hold: [bool; 5] = std::array::from_fn(|i| mask & (1 << i) != 0);
+            let held: Vec<Card> = dealt
+                .iter()
+                .zip(hold.iter())
+                .filter(|&(_, &held)| held)
+                .map(|(&card, _)| card)
+                .collect();
+
+            let draws = 5 - held.len();
+            let mut final_hand = held.clone();
+            final_hand.resize(5, dealt[0]);
+
+            let mut total = T::default();
+            for_each_combination(unseen, draws, |drawn| {
+                final_hand[held.len()..].copy_from_slice(drawn);
+                add(&mut total, &final_hand);
+            });
+
+            HoldOutcome { hold, held, draws: binomial(unseen.len(), draws), total }
+        })
+        .collect()
+}
+
+// Exact expected value of every hold, computed by drawing every possible
+// replacement from the 47 unseen cards. Sorted from best to worst.
+pub fn hold_options(dealt: &[Card], paytable: &Paytable) -> Result<Vec<HoldOption>, &'static str> {
+    if dealt.len() != 5 {
+        return Err("A hand must contain exactly 5 cards");
+    }
+
+    let mut deck = Deck::new();
+    deck.remove(dealt)?;
+
+    let mut options: Vec<HoldOption> = hold_outcomes(dealt, deck.cards(), |total: &mut u64, final_hand| {
+        *total += paytable.payout(final_hand) as u64;
+    })
+    .into_iter()
+    .map(|outcome| HoldOption {
+        hold: outcome.hold,
+        held: outcome.held,
+        expected_value: outcome.total as f64 / outcome.draws as f64,
+    })
+    .collect();
+
+    options.sort_by(|a, b| b.expected_value.total_cmp(&a.expected_value));
+    Ok(options)
+}
+
+pub fn optimal_hold(dealt: &[Card], paytable: &Paytable) -> Result<HoldOption, &'static str> {
+    Ok(hold_options(dealt, paytable)?.remove(0))
+}
+
+// Long-run return of the paytable under perfect play, as a fraction of the
+// amount bet (0.9954 for 9/6 Jacks or Better). Every one of the 2,598,960
+// deals is played optimally.
+//
+// Rather than enumerating draws for every deal, the payouts of all final
+// hands containing each subset of cards are tallied once; the total for a
+// hold then follows by inclusion-exclusion over the discarded cards. Deals
+// that only differ by a permutation of suits have the same best hold, so
+// only one deal per suit pattern is evaluated.
+pub fn full_cycle_return(paytable: &Paytable) -> f64 {
+    let deck: Vec<usize> = (0..52).collect();
+    let mut totals: Vec<Vec<u64>> = (0..=5).map(|k| vec![0; binomial(52, k) as usize]).collect();
+    let mut patterns: HashMap<u64, ([usize; 5], u64)> = HashMap::new();
+
+    let mut cards = [Card::from_index(0); 5];
+    let mut subset = [0usize; 5];
+    for_each_combination(&deck, 5, |hand| {
+        for (card, &index) in cards.iter_mut().zip(hand) {
+            *card = Card::from_index(index);
+        }
+        let pay = paytable.payout(&cards) as u64;
+
+        for mask in 0..32usize {
+            let len = fill_subset(hand, mask, &mut subset);
+            totals[len][combination_index(&subset[..len])] += pay;
+        }
+
+        let hand: [usize; 5] = hand.try_into().unwrap();
+        patterns.entry(suit_pattern(&hand)).or_insert((hand, 0)).1 += 1;
+    });
+
+    let total_return: f64 = patterns
+        .values()
+        .map(|(hand, count)| {
+            // Payout total of the final hands containing each subset of the deal
+            let containing: Vec<i64> = (0..32usize)
+                .map(|mask| {
+                    let len = fill_subset(hand, mask, &mut subset);
+                    totals[len][combination_index(&subset[..len])] as i64
+                })
+                .collect();
+
+            let best = (0..32usize)
+                .map(|hold| {
+                    let draws = 5 - hold.count_ones() as usize;
+                    let total: i64 = (0..32usize)
+                        .filter(|superset| superset & hold == hold)
+                        .map(|superset| {
+                            let extra = (superset & !hold).count_ones();
+                            if extra % 2 == 0 {
+                                containing[superset]
+                            } else {
+                                -containing[superset]
+                            }
+                        })
+                        .sum();
+                    total as f64 / binomial(47, draws) as f64
+                })
+                .fold(0.0, f64::max);
+
+            best * *count as f64
+        })
+        .sum();
+
+    total_return / binomial(52, 5) as f64
+}
+
+// Copies the cards selected by `mask` into `subset`, keeping them sorted
+fn fill_subset(hand: &[usize], mask: usize, subset: &mut [usize; 5]) -> usize {
+    let mut len = 0;
+    for (i, &index) in hand.iter().enumerate() {
+        if mask & (1 << i) != 0 {
+            subset[len] = index;
+            len += 1;
+        }
+    }
+    len
+}
+
+// Identical for deals that only differ by a permutation of suits
+fn suit_pattern(hand: &[usize; 5]) -> u64 {
+    let mut masks = [0u64; 4];
+    for &index in hand {
+        masks[index / 13] |= 1 << (index % 13);
+    }
+    masks.sort_unstable();
+    masks.iter().fold(0, |key, &mask| (key << 13) | mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::parse_cards;
+
+    #[test]
+    fn test_hold_options_covers_every_hold() {
+        let dealt = parse_cards("AS KS QS JS 10S").unwrap();
+        let options = hold_options(&dealt, &Paytable::jacks_or_better()).unwrap();
+
+        assert_eq!(options.len(), 32);
+        assert_eq!(options[0].hold, [true; 5]);
+        assert_eq!(options[0].expected_value, 800.0);
+        assert!(options.windows(2).all(|pair| pair[0].expected_value >= pair[1].expected_value));
+    }
+
+    #[test]
+    fn test_optimal_hold() {
+        // Four to a royal beats the made flush
+        let dealt = parse_cards("AH KH QH JH 3H").unwrap();
+        let best = optimal_hold(&dealt, &Paytable::jacks_or_better()).unwrap();
+        assert_eq!(best.hold, [true, true, true, true, false]);
+        // 1 royal, 7 flushes, 3 straights and 12 high pairs among 47 draws
+        let expected = (800.0 + 7.0 * 6.0 + 3.0 * 4.0 + 12.0) / 47.0;
+        assert!((best.expected_value - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_hold_options_rejects_invalid_hands() {
+        let paytable = Paytable::jacks_or_better();
+        assert!(hold_options(&parse_cards("AH KH QH JH").unwrap(), &paytable).is_err());
+        let mut dealt = parse_cards("AH KH QH JH").unwrap();
+        dealt.push(dealt[0]);
+        assert!(hold_options(&dealt, &paytable).is_err());
+    }
+
+    #[test]
+    fn test_suit_pattern() {
+        let index = |cards: &str| -> [usize; 5] {
+            let cards = parse_cards(cards).unwrap();
+            let mut indexes: Vec<usize> = cards.iter().map(|card| card.index()).collect();
+            indexes.sort();
+            indexes.try_into().unwrap()
+        };
+        assert_eq!(suit_pattern(&index("AH KH 2D 3S 4S")), suit_pattern(&index("AC KC 2S 3D 4D")));
+        assert_ne!(suit_pattern(&index("AH KH 2D 3S 4S")), suit_pattern(&index("AH KD 2H 3S 4S")));
+    }
+
+    #[test]
+    fn test_full_cycle_return() {
+        let jacks_or_better = full_cycle_return(&Paytable::jacks_or_better());
+        assert!((jacks_or_better - 0.995439).abs() < 1e-6);
+
+        let bonus_poker = full_cycle_return(&Paytable::bonus_poker());
+        assert!((bonus_poker - 0.991660).abs() < 1e-6);
+
+        let deuces_wild = full_cycle_return(&Paytable::deuces_wild());
+        assert!((deuces_wild - 1.007620).abs() < 1e-6);
+    }
+}
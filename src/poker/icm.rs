@@ -0,0 +1,215 @@
+use super::betting::EquitySource;
+
+pub const MAX_ICM_PLAYERS: usize = 10;
+
+// Prize equity of every player under the Independent Chip Model: the chance
+// of finishing first is the share of the chips, and each next place goes the
+// same way among the players left. Places past the end of `payouts` pay
+// nothing; busted players (no chips) share the last places evenly.
+pub fn icm_equity(stacks: &[f64], payouts: &[f64]) -> Result<Vec<f64>, &'static str> {
+    let players = stacks.len();
+    if players == 0 || players > MAX_ICM_PLAYERS {
+        return Err("ICM needs between 1 and 10 players");
+    }
+    if stacks.iter().any(|stack| !stack.is_finite() || *stack < 0.0) {
+        return Err("Stacks can't be negative");
+    }
+    if payouts.iter().any(|payout| !payout.is_finite() || *payout < 0.0) {
+        return Err("Payouts can't be negative");
+    }
+    let payout = |place: usize| payouts.get(place).copied().unwrap_or(0.0);
+
+    // `reach[mask]` is the chance that the players in `mask` take the first
+    // places, in any order; each subset is worked out once from the smaller
+    // ones, 2^n entries instead of n! finishing orders
+    let full = (1usize << players) - 1;
+    let mut reach = vec![0.0; full + 1];
+    reach[0] = 1.0;
+    let mut equities = vec![0.0; players];
+
+    for mask in 0..full {
+        if reach[mask] == 0.0 {
+            continue;
+        }
+        let placed = mask.count_ones() as usize;
+        let left: Vec<usize> = (0..players).filter(|&player| mask & (1 << player) == 0).collect();
+        let left_chips: f64 = left.iter().map(|&player| stacks[player]).sum();
+
+        for &player in &left {
+            let chance = if left_chips > 0.0 {
+                stacks[player] / left_chips
+            } else {
+                1.0 / left.len() as f64
+            };
+            let next = reach[mask] * chance;
+            equities[player] += next * payout(placed);
+            reach[mask | (1 << player)] += next;
+        }
+    }
+    Ok(equities)
+}
+
+// Facing an all-in. `stacks` are the chips each player has behind, after the
+// blinds and the shove; `pot` is everything in the middle and `to_call` the
+// amount the hero must put in to match the shove.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AllInSpot {
+    pub stacks: Vec<f64>,
+    pub payouts: Vec<f64>,
+    pub hero: usize,
+    pub villain: usize,
+    pub pot: f64,
+    pub to_call: f64,
+}
+
+// The hero's prize equity after each outcome
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct IcmDecision {
+    pub equity: f64,
+    pub fold: f64,
+    pub call_win: f64,
+    pub call_lose: f64,
+    pub call: f64,
+    // Chance of winning needed for calling to be worth folding, in prize
+    // equity and in chips
+    pub required_equity: f64,
+    pub chip_required_equity: f64,
+}
+
+impl IcmDecision {
+    pub fn should_call(&self) -> bool {
+        self.call > self.fold
+    }
+}
+
+impl AllInSpot {
+    pub fn decide(&self, source: &EquitySource) -> Result<IcmDecision, &'static str> {
+        let players = self.stacks.len();
+        if self.hero >= players || self.villain >= players || self.hero == self.villain {
+            return Err("Hero and villain must be two different players");
+        }
+        if !(self.pot.is_finite() && self.to_call.is_finite() && self.pot >= 0.0 && self.to_call > 0.0) {
+            return Err("The pot can't be negative and the amount to call must be positive");
+        }
+        let equity = source.equity()?;
+
+        // A short hero only covers part of the shove; the rest goes back
+        let call = self.to_call.min(self.stacks[self.hero]);
+        let refund = self.to_call - call;
+        let pot = self.pot - refund + call;
+
+        let hero_equity = |hero_chips: f64, villain_chips: f64| -> Result<f64, &'static str> {
+            let mut stacks = self.stacks.clone();
+            stacks[self.hero] = hero_chips;
+            stacks[self.villain] = villain_chips;
+            Ok(icm_equity(&stacks, &self.payouts)?[self.hero])
+        };
+        let hero_stack = self.stacks[self.hero];
+        let villain_stack = self.stacks[self.villain];
+
+        let fold = hero_equity(hero_stack, villain_stack + self.pot)?;
+        let call_win = hero_equity(hero_stack - call + pot, villain_stack + refund)?;
+        let call_lose = hero_equity(hero_stack - call, villain_stack + refund + pot)?;
+
+        let required_equity = if call_win > call_lose {
+            ((fold - call_lose) / (call_win - call_lose)).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        Ok(IcmDecision {
+            equity,
+            fold,
+            call_win,
+            call_lose,
+            call: equity * call_win + (1.0 - equity) * call_lose,
+            required_equity,
+            chip_required_equity: call / pot,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_icm_equity() {
+        // Winner takes all: equity is the share of chips
+        assert_close(&icm_equity(&[30.0, 20.0, 50.0], &[100.0]).unwrap(), &[30.0, 20.0, 50.0]);
+
+        // Equal stacks share everything evenly
+        let even = icm_equity(&[10.0; 4], &[50.0, 30.0, 20.0]).unwrap();
+        assert_close(&even, &[25.0; 4]);
+
+        // Worked out by hand: 5000/3000/2000 for 50/30/20
+        let equities = icm_equity(&[5000.0, 3000.0, 2000.0], &[50.0, 30.0, 20.0]).unwrap();
+        let first = [0.5, 0.3, 0.2];
+        let second = [
+            0.3 * 5.0 / 7.0 + 0.2 * 5.0 / 8.0,
+            0.5 * 3.0 / 5.0 + 0.2 * 3.0 / 8.0,
+            0.5 * 2.0 / 5.0 + 0.3 * 2.0 / 7.0,
+        ];
+        let expected: Vec<f64> =
+            (0..3).map(|i| 50.0 * first[i] + 30.0 * second[i] + 20.0 * (1.0 - first[i] - second[i])).collect();
+        assert_close(&equities, &expected);
+        assert!((equities.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ten_players_and_busted_stacks() {
+        let stacks: Vec<f64> = (1..=10).map(|i| i as f64 * 1000.0).collect();
+        let payouts = [40.0, 25.0, 15.0, 10.0, 6.0, 4.0];
+        let equities = icm_equity(&stacks, &payouts).unwrap();
+        assert!((equities.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+        assert!(equities.windows(2).all(|pair| pair[0] < pair[1]));
+        // Big stacks are worth less than their share of the chips
+        assert!(equities[9] < 100.0 * 10.0 / 55.0);
+
+        // A busted player takes the last place
+        assert_close(&icm_equity(&[0.0, 10.0, 10.0], &[50.0, 30.0, 20.0]).unwrap(), &[20.0, 40.0, 40.0]);
+    }
+
+    #[test]
+    fn test_bubble_call() {
+        // Four left with 2500 each, three paid; the button shoves and the big
+        // blind can call. Chip-wise 47.5% would do, but busting on the bubble
+        // costs more than doubling up gains.
+        let spot = AllInSpot {
+            stacks: vec![0.0, 2450.0, 2400.0, 2500.0],
+            payouts: vec![50.0, 30.0, 20.0],
+            hero: 2,
+            villain: 0,
+            pot: 2500.0 + 50.0 + 100.0,
+            to_call: 2400.0,
+        };
+        let decision = spot.decide(&EquitySource::Known(0.5)).unwrap();
+        assert!((decision.chip_required_equity - 2400.0 / 5050.0).abs() < 1e-12);
+        assert!(decision.required_equity > 0.55);
+        assert!(!decision.should_call());
+        assert_eq!(decision.call_lose, 0.0);
+        assert!(spot.decide(&EquitySource::Known(0.8)).unwrap().should_call());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(icm_equity(&[], &[100.0]).is_err());
+        assert!(icm_equity(&[1.0; 11], &[100.0]).is_err());
+        assert!(icm_equity(&[1.0, -1.0], &[100.0]).is_err());
+        let spot = AllInSpot {
+            stacks: vec![100.0, 100.0],
+            payouts: vec![100.0],
+            hero: 0,
+            villain: 0,
+            pot: 10.0,
+            to_call: 10.0,
+        };
+        assert!(spot.decide(&EquitySource::Known(0.5)).is_err());
+    }
+}
@@ -0,0 +1,394 @@
+use super::card::{Card, Rank, Suit};
+use super::combinations::binomial;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+const MAX_ROUNDS: usize = 8;
+
+// Hands whose cards only differ by a renaming of the suits play the same way:
+// AhKh and AsKs before the flop, or AhKh on 2h7c9d and AsKs on 2s7h9d. The
+// indexer maps every hand to one of the hands equivalent to it, and numbers
+// the classes densely from 0, street by street.
+//
+// A hand is described suit by suit: for each round (hole cards, flop, turn...)
+// the ranks dealt in that suit. Suits are sorted by how many cards they get in
+// each round, then by the index of their ranks, and renamed in that order.
+#[derive(Debug, Clone)]
+pub struct HandIndexer {
+    rounds: Vec<usize>,
+    // Ways to spread the cards of each round over the suits, suits sorted
+    configurations: Vec<Configuration>,
+    lookup: HashMap<[Shape; 4], usize>,
+    size: u64,
+}
+
+// Cards of one suit in each round, four bits per round with the first round
+// in the highest bits, so shapes compare like the lists of counts
+type Shape = u32;
+
+// Ranks of one suit dealt in each round, as bit sets (bit 0 for the two)
+type SuitSets = [u16; MAX_ROUNDS];
+
+// A suit's place in the canonical order: its shape, most cards first, then
+// its index among suits of that shape
+type SuitKey = (Reverse<Shape>, u64, usize);
+
+#[derive(Debug, Clone)]
+struct Configuration {
+    shapes: [Shape; 4],
+    // Index of the first hand of the configuration
+    offset: u64,
+}
+
+// Number of multisets of `k` values below `n`
+fn multichoose(n: u64, k: usize) -> u64 {
+    if k == 0 {
+        1
+    } else {
+        binomial(n as usize + k - 1, k)
+    }
+}
+
+// Index among multisets of the same size, from values sorted in increasing
+// order: adding its position to each value makes them distinct
+fn multiset_index(values: impl Iterator<Item = u64>) -> u64 {
+    values.enumerate().map(|(i, value)| binomial(value as usize + i, i + 1)).sum()
+}
+
+// Reverse of the colex index of `k` distinct values
+fn combination_from_index(mut index: u64, k: usize) -> Vec<usize> {
+    let mut values = vec![0; k];
+    for i in (1..=k).rev() {
+        // Largest value whose binomial fits
+        let (mut low, mut high) = (i - 1, i - 1);
+        while binomial(high + 1, i) <= index {
+            high = high * 2 + 1;
+        }
+        while low < high {
+            let middle = (low + high).div_ceil(2);
+            if binomial(middle, i) <= index {
+                low = middle;
+            } else {
+                high = middle - 1;
+            }
+        }
+        values[i - 1] = low;
+        index -= binomial(low, i);
+    }
+    values
+}
+
+impl HandIndexer {
+    // `rounds` gives the number of cards dealt in each round, e.g. [2, 3] for
+    // hole cards and a flop, or [3] for a flop alone
+    pub fn new(rounds: &[usize]) -> Result<Self, &'static str> {
+        if rounds.is_empty() || rounds.contains(&0) || rounds.iter().sum::<usize>() > MAX_ROUNDS {
+            return Err("Between 1 and 8 cards, in rounds of at least one card");
+        }
+
+        // Every shape a suit can have, from the most cards down
+        let mut shapes: Vec<Shape> = vec![0];
+        for &cards in rounds {
+            shapes = shapes.into_iter().flat_map(|shape| (0..=cards as Shape).map(move |count| shape << 4 | count)).collect();
+        }
+        shapes.sort_by_key(|&shape| Reverse(shape));
+
+        let mut indexer = HandIndexer { rounds: rounds.to_vec(), configurations: Vec::new(), lookup: HashMap::new(), size: 0 };
+        indexer.add_configurations(&shapes, 0, &mut Vec::new(), rounds.to_vec());
+        Ok(indexer)
+    }
+
+    pub fn preflop() -> Self {
+        HandIndexer::new(&[2]).unwrap()
+    }
+
+    pub fn flop() -> Self {
+        HandIndexer::new(&[2, 3]).unwrap()
+    }
+
+    pub fn turn() -> Self {
+        HandIndexer::new(&[2, 3, 1]).unwrap()
+    }
+
+    pub fn river() -> Self {
+        HandIndexer::new(&[2, 3, 1, 1]).unwrap()
+    }
+
+    // Picks the shape of each suit in turn, never going back up the list so
+    // each configuration comes once
+    fn add_configurations(&mut self, shapes: &[Shape], first: usize, chosen: &mut Vec<Shape>, left: Vec<usize>) {
+        if chosen.len() == 4 {
+            if left.iter().all(|&count| count == 0) {
+                let shapes: [Shape; 4] = chosen.as_slice().try_into().unwrap();
+                self.lookup.insert(shapes, self.configurations.len());
+                self.configurations.push(Configuration { shapes, offset: self.size });
+                self.size += groups(&shapes).map(|group| multichoose(self.suit_size(group[0]), group.len())).product::<u64>();
+            }
+            return;
+        }
+        for (i, &shape) in shapes.iter().enumerate().skip(first) {
+            let counts = self.counts(shape);
+            if counts.iter().zip(&left).all(|(count, left)| count <= left) {
+                let left = left.iter().zip(&counts).map(|(left, count)| left - count).collect();
+                chosen.push(shape);
+                self.add_configurations(shapes, i, chosen, left);
+                chosen.pop();
+            }
+        }
+    }
+
+    fn counts(&self, shape: Shape) -> Vec<usize> {
+        let rounds = self.rounds.len();
+        (0..rounds).map(|round| (shape >> (4 * (rounds - 1 - round)) & 0xF) as usize).collect()
+    }
+
+    fn shape(&self, sets: &SuitSets) -> Shape {
+        sets[..self.rounds.len()].iter().fold(0, |shape, set| shape << 4 | set.count_ones())
+    }
+
+    fn suit_size(&self, shape: Shape) -> u64 {
+        let mut used = 0;
+        self.counts(shape)
+            .into_iter()
+            .map(|count| {
+                let ways = binomial(13 - used, count);
+                used += count;
+                ways
+            })
+            .product()
+    }
+
+    // Index of a suit's rank sets among those with the same shape; each
+    // round's ranks are numbered among the ranks still unused, and the rounds
+    // combined with mixed radix
+    fn suit_index(&self, sets: &SuitSets) -> u64 {
+        let (mut index, mut multiplier, mut used) = (0, 1, 0u16);
+        for &set in &sets[..self.rounds.len()] {
+            let (mut colex, mut count) = (0, 0);
+            for rank in 0..13 {
+                if set & (1 << rank) != 0 {
+                    count += 1;
+                    let position = rank - (used & ((1 << rank) - 1)).count_ones() as usize;
+                    colex += binomial(position, count);
+                }
+            }
+            index += colex * multiplier;
+            multiplier *= binomial(13 - used.count_ones() as usize, count);
+            used |= set;
+        }
+        index
+    }
+
+    fn suit_from_index(&self, shape: Shape, mut index: u64) -> SuitSets {
+        let mut sets = [0; MAX_ROUNDS];
+        let mut used = 0u16;
+        for (round, count) in self.counts(shape).into_iter().enumerate() {
+            let ways = binomial(13 - used.count_ones() as usize, count);
+            let unused: Vec<usize> = (0..13).filter(|rank| used & (1 << rank) == 0).collect();
+            sets[round] = combination_from_index(index % ways, count)
+                .into_iter()
+                .fold(0, |set, position| set | (1 << unused[position]));
+            index /= ways;
+            used |= sets[round];
+        }
+        sets
+    }
+
+    pub fn rounds(&self) -> &[usize] {
+        &self.rounds
+    }
+
+    // Number of classes of equivalent hands, e.g. 1,286,792 for hole cards
+    // and a flop
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    // The suits in canonical order, with their shapes and indexes
+    fn describe(&self, cards: &[Card]) -> Result<[SuitKey; 4], &'static str> {
+        if cards.len() != self.rounds.iter().sum::<usize>() {
+            return Err("Wrong number of cards for the rounds");
+        }
+        let mut sets = [[0u16; MAX_ROUNDS]; 4];
+        let mut seen = 0u64;
+        let mut start = 0;
+        for (round, &count) in self.rounds.iter().enumerate() {
+            for card in &cards[start..start + count] {
+                if seen & (1 << card.index()) != 0 {
+                    return Err("Duplicate card");
+                }
+                seen |= 1 << card.index();
+                sets[card.suit as usize][round] |= 1 << (card.rank as usize - 2);
+            }
+            start += count;
+        }
+
+        let mut order = [0, 1, 2, 3].map(|suit| (Reverse(self.shape(&sets[suit])), self.suit_index(&sets[suit]), suit));
+        order.sort_unstable();
+        Ok(order)
+    }
+
+    // Dense index of the class of a hand, whose cards are given round by
+    // round (hole cards first)
+    pub fn index(&self, cards: &[Card]) -> Result<u64, &'static str> {
+        let order = self.describe(cards)?;
+        let configuration = &self.configurations[self.lookup[&order.map(|(Reverse(shape), _, _)| shape)]];
+
+        // One multiset of suit indexes per group of suits with the same
+        // shape, combined with mixed radix
+        let (mut index, mut multiplier, mut position) = (0, 1, 0);
+        for group in groups(&configuration.shapes) {
+            let values = order[position..position + group.len()].iter().map(|&(_, index, _)| index);
+            index += multiset_index(values) * multiplier;
+            multiplier *= multichoose(self.suit_size(group[0]), group.len());
+            position += group.len();
+        }
+        Ok(configuration.offset + index)
+    }
+
+    // The canonical hand of a class, round by round; within a round, cards
+    // are sorted by suit then rank
+    pub fn unindex(&self, index: u64) -> Result<Vec<Card>, &'static str> {
+        if index >= self.size {
+            return Err("Index out of range");
+        }
+        let found = self.configurations.partition_point(|configuration| configuration.offset <= index) - 1;
+        let configuration = &self.configurations[found];
+
+        let mut sets: Vec<SuitSets> = Vec::with_capacity(4);
+        let mut rest = index - configuration.offset;
+        for group in groups(&configuration.shapes) {
+            let ways = multichoose(self.suit_size(group[0]), group.len());
+            let distinct = combination_from_index(rest % ways, group.len());
+            rest /= ways;
+            for (i, value) in distinct.into_iter().enumerate() {
+                sets.push(self.suit_from_index(group[0], (value - i) as u64));
+            }
+        }
+
+        let mut cards = Vec::with_capacity(self.rounds.iter().sum());
+        for round in 0..self.rounds.len() {
+            for (suit, suit_sets) in Suit::ALL.iter().zip(&sets) {
+                for rank in 0..13 {
+                    if suit_sets[round] & (1 << rank) != 0 {
+                        cards.push(Card { rank: Rank::ALL[rank], suit: *suit });
+                    }
+                }
+            }
+        }
+        Ok(cards)
+    }
+
+    // The suit each suit of the hand becomes in its canonical form, indexed
+    // like `Suit::ALL`
+    pub fn suit_permutation(&self, cards: &[Card]) -> Result<[Suit; 4], &'static str> {
+        let order = self.describe(cards)?;
+        let mut permutation = [Suit::Hearts; 4];
+        for (position, &(_, _, suit)) in order.iter().enumerate() {
+            permutation[suit] = Suit::ALL[position];
+        }
+        Ok(permutation)
+    }
+
+    // The hand with its suits renamed to the canonical ones; the same as
+    // `unindex(index(cards))`
+    pub fn canonicalize(&self, cards: &[Card]) -> Result<Vec<Card>, &'static str> {
+        let permutation = self.suit_permutation(cards)?;
+        let mut canonical: Vec<Card> =
+            cards.iter().map(|card| Card { rank: card.rank, suit: permutation[card.suit as usize] }).collect();
+        let mut start = 0;
+        for &count in &self.rounds {
+            canonical[start..start + count].sort_by_key(|card| card.index());
+            start += count;
+        }
+        Ok(canonical)
+    }
+}
+
+// Runs of suits with the same shape
+fn groups(shapes: &[Shape; 4]) -> impl Iterator<Item = &[Shape]> {
+    shapes.chunk_by(|a, b| a == b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::parse_cards;
+    use super::super::rng::Rng;
+
+    fn cards(text: &str) -> Vec<Card> {
+        parse_cards(text).unwrap()
+    }
+
+    #[test]
+    fn test_sizes() {
+        assert_eq!(HandIndexer::preflop().size(), 169);
+        assert_eq!(HandIndexer::flop().size(), 1_286_792);
+        assert_eq!(HandIndexer::turn().size(), 55_190_538);
+        assert_eq!(HandIndexer::river().size(), 2_428_287_420);
+        // Boards alone
+        assert_eq!(HandIndexer::new(&[3]).unwrap().size(), 1_755);
+        assert_eq!(HandIndexer::new(&[5]).unwrap().size(), 134_459);
+    }
+
+    #[test]
+    fn test_suit_equivalent_hands() {
+        let flop = HandIndexer::flop();
+        let hearts = flop.index(&cards("AH KH 2H 7C 9D")).unwrap();
+        assert_eq!(flop.index(&cards("AS KS 2S 7H 9D")).unwrap(), hearts);
+        assert_eq!(flop.index(&cards("KS AS 9C 2S 7H")).unwrap(), hearts);
+        assert_ne!(flop.index(&cards("AS KS 2H 7C 9D")).unwrap(), hearts);
+
+        // Hole cards and board don't mix: AhKh on a board vs Ah on it
+        assert_ne!(flop.index(&cards("AH 2H KH 7C 9D")).unwrap(), hearts);
+
+        let canonical = flop.canonicalize(&cards("AS KS 2S 7H 9D")).unwrap();
+        assert_eq!(canonical, flop.unindex(hearts).unwrap());
+        assert_eq!(flop.canonicalize(&canonical).unwrap(), canonical);
+    }
+
+    #[test]
+    fn test_index_is_a_bijection() {
+        for rounds in [&[2][..], &[3], &[2, 3]] {
+            let indexer = HandIndexer::new(rounds).unwrap();
+            for index in 0..indexer.size() {
+                let hand = indexer.unindex(index).unwrap();
+                assert_eq!(indexer.index(&hand).unwrap(), index);
+                assert_eq!(indexer.canonicalize(&hand).unwrap(), hand);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_hands() {
+        let river = HandIndexer::river();
+        let mut rng = Rng::new(17);
+        let mut deck: Vec<Card> = (0..52).map(Card::from_index).collect();
+        for _ in 0..2_000 {
+            rng.shuffle(&mut deck);
+            let hand = &deck[..7];
+            let index = river.index(hand).unwrap();
+            assert!(index < river.size());
+            let canonical = river.canonicalize(hand).unwrap();
+            assert_eq!(canonical, river.unindex(index).unwrap());
+
+            // Any renaming of the suits gives the same class
+            let permutation = river.suit_permutation(hand).unwrap();
+            let renamed: Vec<Card> =
+                hand.iter().map(|card| Card { rank: card.rank, suit: permutation[card.suit as usize] }).collect();
+            assert_eq!(river.index(&renamed).unwrap(), index);
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(HandIndexer::new(&[]).is_err());
+        assert!(HandIndexer::new(&[2, 0]).is_err());
+        let flop = HandIndexer::flop();
+        assert!(flop.index(&cards("AH KH 2H 7C")).is_err());
+        let mut duplicate = cards("AH KH 2H 7C");
+        duplicate.push(duplicate[0]);
+        assert!(flop.index(&duplicate).is_err());
+        assert!(flop.unindex(flop.size()).is_err());
+    }
+}
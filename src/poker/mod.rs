@@ -1,8 +1,59 @@
+pub mod betting;
+pub mod bucketing;
 pub mod card;
+pub mod combinations;
+pub mod combinatorics;
+pub mod deck;
+pub mod draw;
+pub mod equity;
 pub mod hand;
+pub mod hand_strength;
+pub mod hold;
+pub mod icm;
+pub mod isomorphism;
+pub mod nuts;
+pub mod ofc;
+pub mod outs;
 pub mod parser;
+pub mod percentile;
+pub mod preflop;
+pub mod push_fold;
+pub mod random_opponents;
+pub mod range;
+pub mod range_equity;
+pub mod rng;
+pub mod simulation;
+pub mod starting_hands;
+pub mod texture;
+pub mod value;
+pub mod video_poker;
 
 // Re-export commonly used items for easier access
+pub use betting::{CallAnalysis, EquitySource, PotOdds};
+pub use bucketing::{equity_histogram, BucketSettings, EquityBuckets};
 pub use card::{Card, Rank, Suit, HandType};
+pub use combinatorics::{blocker_effect, combo_count, deal_probability, range_combo_count, range_deal_probability, BlockerEffect};
+pub use deck::Deck;
+pub use draw::{discard_options, recommended_discard, DiscardOption};
+pub use equity::{exact_equity, exact_equity_with_progress, monte_carlo_equity, EquityResult, PlayerEquity};
 pub use hand::Hand;
-pub use parser::{parse_card, parse_hand}; 
\ No newline at end of file
+pub use hand_strength::{hand_strength, HandStrength};
+pub use hold::{full_cycle_return, hold_options, optimal_hold, HoldOption};
+pub use icm::{icm_equity, AllInSpot, IcmDecision, MAX_ICM_PLAYERS};
+pub use isomorphism::HandIndexer;
+pub use nuts::{HoldingPosition, NutRanking, StrengthBucket};
+pub use ofc::{OfcHand, Row};
+pub use outs::{find_outs, Outs};
+pub use parser::{parse_card, parse_cards, parse_hand, parse_hole_cards};
+pub use percentile::{category_probability, enumerate_frequencies, hand_percentile, CategoryFrequencies, HandPercentile};
+pub use preflop::PreflopTable;
+pub use push_fold::{solve_heads_up, PushFoldSolution};
+pub use random_opponents::{equity_vs_random, generate_vs_random_table, precomputed_vs_random, MultiwayOdds, MAX_OPPONENTS};
+pub use range::{parse_range, HandClass, HandClassKind, Range};
+pub use range_equity::{exact_range_equity, monte_carlo_range_equity, ComboEquity, RangeEquity, RangeEquityResult};
+pub use rng::Rng;
+pub use simulation::{Merge, Simulation};
+pub use starting_hands::{chen_score, sklansky_group, starting_hand_ranking, starting_hand_rankings, StartingHandRanking};
+pub use texture::{BoardTexture, Connectedness, FlushDraw, Pairing, StraightDraw, SuitTexture, Wetness};
+pub use value::HandValue;
+pub use video_poker::{Game, Paytable, Payline, VideoPoker};
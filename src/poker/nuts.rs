@@ -0,0 +1,159 @@
+use super::card::Card;
+use super::combinations::for_each_combination;
+use super::deck::Deck;
+use super::value::HandValue;
+use std::fmt;
+
+// Two-card holdings making exactly the same hand with the board
+#[derive(Debug, PartialEq, Clone)]
+pub struct StrengthBucket {
+    pub value: HandValue,
+    pub combos: Vec<[Card; 2]>,
+}
+
+// Where a holding stands among every holding possible on a board
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct HoldingPosition {
+    // 1 for the nuts, 2 for the second nuts...
+    pub rank: usize,
+    // Combos making a better hand
+    pub beaten_by: usize,
+    // Other combos making the same hand
+    pub tied_with: usize,
+}
+
+// "the nuts", or e.g. "3rd nuts, beaten by 14 combos"
+impl fmt::Display for HoldingPosition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.rank == 1 {
+            return write!(f, "the nuts");
+        }
+        let suffix = match (self.rank % 10, self.rank % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+        let plural = if self.beaten_by == 1 { "" } else { "s" };
+        write!(f, "{}{} nuts, beaten by {} combo{}", self.rank, suffix, self.beaten_by, plural)
+    }
+}
+
+// Every holding on a board, from the nuts down
+#[derive(Debug, PartialEq, Clone)]
+pub struct NutRanking {
+    pub board: Vec<Card>,
+    pub buckets: Vec<StrengthBucket>,
+}
+
+impl NutRanking {
+    // Ranks the holdings by the hand they make with the board as it is, so on
+    // the flop and turn draws count for nothing
+    pub fn new(board: &[Card]) -> Result<Self, &'static str> {
+        if board.len() < 3 || board.len() > 5 {
+            return Err("A board has 3 to 5 cards");
+        }
+        let mut deck = Deck::new();
+        deck.remove(board).map_err(|_| "Duplicate card")?;
+
+        let mut cards = board.to_vec();
+        cards.extend_from_slice(&board[..2]);
+        let mut holdings: Vec<(HandValue, [Card; 2])> = Vec::new();
+        for_each_combination(deck.cards(), 2, |holding| {
+            cards[board.len()..].copy_from_slice(holding);
+            holdings.push((HandValue::from_cards(&cards), [holding[0], holding[1]]));
+        });
+        holdings.sort_by_key(|&(value, _)| std::cmp::Reverse(value));
+
+        let mut buckets: Vec<StrengthBucket> = Vec::new();
+        for (value, combo) in holdings {
+            match buckets.last_mut() {
+                Some(bucket) if bucket.value == value => bucket.combos.push(combo),
+                _ => buckets.push(StrengthBucket { value, combos: vec![combo] }),
+            }
+        }
+
+        Ok(NutRanking { board: board.to_vec(), buckets })
+    }
+
+    // None if the holding shares a card with the board
+    pub fn position(&self, holding: [Card; 2]) -> Option<HoldingPosition> {
+        let same = |combo: &[Card; 2]| combo.contains(&holding[0]) && combo.contains(&holding[1]);
+        let mut beaten_by = 0;
+        for (i, bucket) in self.buckets.iter().enumerate() {
+            if bucket.combos.iter().any(same) {
+                return Some(HoldingPosition { rank: i + 1, beaten_by, tied_with: bucket.combos.len() - 1 });
+            }
+            beaten_by += bucket.combos.len();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::card::HandType;
+    use super::super::parser::{parse_cards, parse_hole_cards};
+
+    fn ranking(board: &str) -> NutRanking {
+        NutRanking::new(&parse_cards(board).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_river_ranking() {
+        let ranking = ranking("2C 7D 9H JS KD");
+        let total: usize = ranking.buckets.iter().map(|bucket| bucket.combos.len()).sum();
+        assert_eq!(total, 1081);
+        assert!(ranking.buckets.windows(2).all(|pair| pair[0].value > pair[1].value));
+
+        // Queen-ten and ten-eight make the only straights, then come the sets
+        assert_eq!(ranking.buckets[0].value.hand_type(), HandType::Straight);
+        assert_eq!(ranking.buckets[0].combos.len(), 16);
+        assert_eq!(ranking.buckets[1].value.hand_type(), HandType::Straight);
+        assert_eq!(ranking.buckets[2].value.hand_type(), HandType::ThreeOfAKind);
+        assert_eq!(ranking.buckets[2].combos.len(), 3);
+    }
+
+    #[test]
+    fn test_holding_position() {
+        let ranking = ranking("2C 7D 9H JS KD");
+        let position = |holding: &str| ranking.position(parse_hole_cards(holding).unwrap());
+
+        let nuts = position("QC 10C").unwrap();
+        assert_eq!(nuts, HoldingPosition { rank: 1, beaten_by: 0, tied_with: 15 });
+        assert_eq!(nuts.to_string(), "the nuts");
+
+        assert_eq!(position("8C 10C").unwrap().to_string(), "2nd nuts, beaten by 16 combos");
+        assert_eq!(position("KS KH").unwrap().to_string(), "3rd nuts, beaten by 32 combos");
+        assert_eq!(position("JH JD").unwrap().to_string(), "4th nuts, beaten by 35 combos");
+        assert_eq!(position("KD AS"), None);
+    }
+
+    #[test]
+    fn test_flop_ranking() {
+        let ranking = ranking("AH KH QH");
+        assert_eq!(ranking.buckets[0].value.hand_type(), HandType::RoyalFlush);
+        assert_eq!(ranking.buckets[0].combos, vec![parse_hole_cards("10H JH").unwrap()]);
+        let total: usize = ranking.buckets.iter().map(|bucket| bucket.combos.len()).sum();
+        assert_eq!(total, 1176);
+    }
+
+    #[test]
+    fn test_ordinal_suffixes() {
+        let position = |rank| HoldingPosition { rank, beaten_by: 1, tied_with: 0 }.to_string();
+        assert_eq!(position(2), "2nd nuts, beaten by 1 combo");
+        assert_eq!(position(11), "11th nuts, beaten by 1 combo");
+        assert_eq!(position(21), "21st nuts, beaten by 1 combo");
+        assert_eq!(position(103), "103rd nuts, beaten by 1 combo");
+    }
+
+    #[test]
+    fn test_invalid_boards() {
+        assert!(NutRanking::new(&parse_cards("AH KH").unwrap()).is_err());
+        let mut board = parse_cards("AH KH QH").unwrap();
+        board.push(board[1]);
+        assert!(NutRanking::new(&board).is_err());
+    }
+}
@@ -0,0 +1,268 @@
+use super::card::{Card, HandType, Rank};
+use super::hand::Hand;
+use super::parser::parse_cards;
+use super::value::HandValue;
+use std::cmp::Ordering;
+
+// Extra points for winning all three rows against the same opponent
+pub const SCOOP_BONUS: i32 = 3;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Row {
+    Top,
+    Middle,
+    Bottom,
+}
+
+impl Row {
+    pub const ALL: [Row; 3] = [Row::Top, Row::Middle, Row::Bottom];
+
+    pub fn size(&self) -> usize {
+        match self {
+            Row::Top => 3,
+            Row::Middle | Row::Bottom => 5,
+        }
+    }
+}
+
+// A complete Open-Face Chinese Poker arrangement of 13 cards
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OfcHand {
+    pub top: Vec<Card>,
+    pub middle: Hand,
+    pub bottom: Hand,
+}
+
+impl OfcHand {
+    pub fn new(top: Vec<Card>, middle: Vec<Card>, bottom: Vec<Card>) -> Result<Self, &'static str> {
+        if top.len() != Row::Top.size() {
+            return Err("The top row must contain exactly 3 cards");
+        }
+
+        let all_cards: Vec<&Card> = top.iter().chain(&middle).chain(&bottom).collect();
+        for (i, card) in all_cards.iter().enumerate() {
+            if all_cards[i + 1..].contains(card) {
+                return Err("Duplicate card");
+            }
+        }
+
+        Ok(OfcHand {
+            top,
+            middle: Hand::new(middle)?,
+            bottom: Hand::new(bottom)?,
+        })
+    }
+
+    pub fn parse(top: &str, middle: &str, bottom: &str) -> Result<Self, &'static str> {
+        OfcHand::new(parse_cards(top)?, parse_cards(middle)?, parse_cards(bottom)?)
+    }
+
+    pub fn row_value(&self, row: Row) -> HandValue {
+        match row {
+            Row::Top => HandValue::from_cards(&self.top),
+            Row::Middle => self.middle.value(),
+            Row::Bottom => self.bottom.value(),
+        }
+    }
+
+    pub fn row_type(&self, row: Row) -> HandType {
+        self.row_value(row).hand_type()
+    }
+
+    // Rows must not get stronger from bottom to top
+    pub fn is_foul(&self) -> bool {
+        self.middle > self.bottom || self.row_value(Row::Top) > self.middle.value()
+    }
+
+    pub fn row_royalty(&self, row: Row) -> u32 {
+        if self.is_foul() {
+            return 0;
+        }
+
+        match row {
+            Row::Top => top_royalty(&self.top),
+            Row::Middle => match self.middle.evaluate() {
+                HandType::ThreeOfAKind => 2,
+                HandType::Straight => 4,
+                HandType::Flush => 8,
+                HandType::FullHouse => 12,
+                HandType::FourOfAKind => 20,
+                HandType::StraightFlush => 30,
+                HandType::RoyalFlush => 50,
+                _ => 0,
+            },
+            Row::Bottom => match self.bottom.evaluate() {
+                HandType::Straight => 2,
+                HandType::Flush => 4,
+                HandType::FullHouse => 6,
+                HandType::FourOfAKind => 10,
+                HandType::StraightFlush => 15,
+                HandType::RoyalFlush => 25,
+                _ => 0,
+            },
+        }
+    }
+
+    pub fn royalties(&self) -> u32 {
+        Row::ALL.iter().map(|&row| self.row_royalty(row)).sum()
+    }
+
+    // Queens or better in the top row of a valid hand earns fantasyland
+    pub fn qualifies_for_fantasyland(&self) -> bool {
+        // QQ is the first top-row holding worth 7 points
+        !self.is_foul() && top_royalty(&self.top) >= 7
+    }
+
+    // Row-by-row result against another player, from this player's side
+    pub fn compare_rows(&self, other: &OfcHand) -> [Ordering; 3] {
+        match (self.is_foul(), other.is_foul()) {
+            (true, true) => [Ordering::Equal; 3],
+            (true, false) => [Ordering::Less; 3],
+            (false, true) => [Ordering::Greater; 3],
+            (false, false) => Row::ALL.map(|row| self.row_value(row).cmp(&other.row_value(row))),
+        }
+    }
+
+    // Points won (or lost, if negative) against a single opponent: one point
+    // per row, the scoop bonus for winning every row, and the royalty difference
+    pub fn score_against(&self, other: &OfcHand) -> i32 {
+        let rows = self.compare_rows(other);
+        let mut points: i32 = rows
+            .iter()
+            .map(|ordering| match ordering {
+                Ordering::Greater => 1,
+                Ordering::Less => -1,
+                Ordering::Equal => 0,
+            })
+            .sum();
+
+        if points == 3 {
+            points += SCOOP_BONUS;
+        } else if points == -3 {
+            points -= SCOOP_BONUS;
+        }
+
+        points + self.royalties() as i32 - other.royalties() as i32
+    }
+}
+
+// Net points for each player when every player is scored against every other
+pub fn score_table(players: &[OfcHand]) -> Vec<i32> {
+    players
+        .iter()
+        .enumerate()
+        .map(|(i, player)| {
+            players
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, other)| player.score_against(other))
+                .sum()
+        })
+        .collect()
+}
+
+// 66 pays 1 up to AA paying 9; trips pay 10 for deuces up to 22 for aces
+fn top_royalty(top: &[Card]) -> u32 {
+    let mut counts = [0u8; 13];
+    for card in top {
+        counts[card.rank as usize - 2] += 1;
+    }
+
+    let ranks = Rank::ALL.iter().zip(counts.iter());
+    for (&rank, &count) in ranks {
+        if count == 3 {
+            return rank as u32 + 8;
+        }
+        if count == 2 && rank >= Rank::Six {
+            return rank as u32 - 5;
+        }
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ofc(top: &str, middle: &str, bottom: &str) -> OfcHand {
+        OfcHand::parse(top, middle, bottom).unwrap()
+    }
+
+    #[test]
+    fn test_ofc_hand_creation_error() {
+        assert!(OfcHand::parse("AH KH", "2C 3C 4C 5C 7D", "8S 8D 9S 9D JC").is_err());
+        assert!(OfcHand::parse("AH KH QH", "2C 3C 4C 5C", "8S 8D 9S 9D JC").is_err());
+        assert!(OfcHand::parse("AH KH QH", "2C 3C 4C 5C 7D", "8S 8D 9S 9D AH").is_err());
+    }
+
+    #[test]
+    fn test_row_types() {
+        let hand = ofc("QH QD 2C", "5S 5C 8D 8C KS", "AH 3H 6H 9H JH");
+        assert_eq!(hand.row_type(Row::Top), HandType::OnePair);
+        assert_eq!(hand.row_type(Row::Middle), HandType::TwoPair);
+        assert_eq!(hand.row_type(Row::Bottom), HandType::Flush);
+        assert!(!hand.is_foul());
+    }
+
+    #[test]
+    fn test_foul_detection() {
+        // Middle row stronger than the bottom
+        assert!(ofc("2H 3D 4C", "AH AD KC KS 2S", "QH QD 5C 6S 7S").is_foul());
+        // Top row stronger than the middle
+        assert!(ofc("KH KD 4C", "QS QC 8D 9C 2D", "AH 3H 6H 9H JH").is_foul());
+        // Same pair in the top and middle rows, decided by kickers
+        assert!(!ofc("KH KD 4C", "KS KC 8D 9C 2D", "AH 3H 6H 9H JH").is_foul());
+        assert!(ofc("KH KD AC", "KS KC QD 9C 2D", "AH 3H 6H 9H JH").is_foul());
+    }
+
+    #[test]
+    fn test_royalties() {
+        let hand = ofc("QH QD 2C", "5S 5C 5D 8C KS", "AH 3H 6H 9H JH");
+        assert_eq!(hand.row_royalty(Row::Top), 7);
+        assert_eq!(hand.row_royalty(Row::Middle), 2);
+        assert_eq!(hand.row_royalty(Row::Bottom), 4);
+        assert_eq!(hand.royalties(), 13);
+
+        let trips_on_top = ofc("2H 2D 2C", "3S 3C 3D 8C 8S", "AH AD AC AS KH");
+        assert_eq!(trips_on_top.row_royalty(Row::Top), 10);
+        assert_eq!(trips_on_top.royalties(), 10 + 12 + 10);
+
+        assert_eq!(ofc("5H 5D 2C", "6S 6C 8D 9C KS", "AH AD AC KD 3S").row_royalty(Row::Top), 0);
+
+        let fouled = ofc("AH AD 2C", "5S 5C 8D 9C KS", "QH 3H 6H 9H JH");
+        assert!(fouled.is_foul());
+        assert_eq!(fouled.royalties(), 0);
+    }
+
+    #[test]
+    fn test_fantasyland() {
+        assert!(ofc("QH QD 2C", "5S 5C 5D 8C KS", "AH 3H 6H 9H JH").qualifies_for_fantasyland());
+        assert!(!ofc("JC JD AC", "5S 5C 5D 8C KS", "AH 3H 6H 9H JH").qualifies_for_fantasyland());
+        assert!(!ofc("AH AD 2C", "5S 5C 8D 9C KS", "QH 3H 6H 9H JH").qualifies_for_fantasyland());
+    }
+
+    #[test]
+    fn test_scoring() {
+        let strong = ofc("QH QD 2C", "5S 5C 5D 8C KS", "AH 3H 6H 9H JH");
+        let weak = ofc("2H 3D 4C", "6S 6C 8D 9C KD", "10S 10D JC JD 2S");
+
+        // Scoop (3 + 3) plus 13 points of royalties
+        assert_eq!(strong.score_against(&weak), 19);
+        assert_eq!(weak.score_against(&strong), -19);
+
+        let fouled = ofc("AH AD 2C", "5S 5C 8D 9C KS", "QH 3H 6H 9H JH");
+        assert_eq!(weak.score_against(&fouled), 6);
+        assert_eq!(fouled.score_against(&fouled.clone()), 0);
+    }
+
+    #[test]
+    fn test_split_rows() {
+        let first = ofc("AH KC 4C", "6S 6C 8D 9C KD", "10S 10D JC JD 2S");
+        let second = ofc("2H 3D 5C", "7S 7H 4S 10H KH", "9S 9H 8S 8H 3C");
+
+        assert_eq!(first.compare_rows(&second), [Ordering::Greater, Ordering::Less, Ordering::Greater]);
+        assert_eq!(first.score_against(&second), 1);
+        assert_eq!(score_table(&[first, second]), vec![1, -1]);
+    }
+}
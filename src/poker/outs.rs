@@ -0,0 +1,180 @@
+use super::card::{Card, HandType};
+use super::combinations::binomial;
+use super::deck::Deck;
+use super::value::HandValue;
+
+// Unseen cards that help a Hold'em hand on the flop or the turn, grouped by
+// the hand they make
+#[derive(Debug, PartialEq, Clone)]
+pub struct Outs {
+    // Strongest resulting hand first, each group's cards in deck order
+    pub groups: Vec<(HandType, Vec<Card>)>,
+    // Cards the next one is dealt from
+    pub unseen: usize,
+    // Cards still to come: 2 on the flop, 1 on the turn
+    pub cards_to_come: usize,
+}
+
+impl Outs {
+    pub fn count(&self) -> usize {
+        self.groups.iter().map(|(_, cards)| cards.len()).sum()
+    }
+
+    pub fn cards(&self) -> Vec<Card> {
+        self.groups.iter().flat_map(|(_, cards)| cards.iter().copied()).collect()
+    }
+
+    // Probability that the next card is an out
+    pub fn next_card_probability(&self) -> f64 {
+        self.count() as f64 / self.unseen as f64
+    }
+
+    // Probability that at least one out comes by the river; runner-runner
+    // hands made by two non-outs are not counted
+    pub fn by_river_probability(&self) -> f64 {
+        let misses = binomial(self.unseen - self.count(), self.cards_to_come);
+        1.0 - misses as f64 / binomial(self.unseen, self.cards_to_come) as f64
+    }
+}
+
+// Every unseen card that improves the hand. Without an opponent a card is an
+// out when it raises the hand's category and the new hand beats both the
+// current one and the hand the board makes alone with the card. A card is
+// dealt to the board for everyone, so that board hand also takes one more
+// unseen card of the same rank when there is one: 88 on K72 gains nothing
+// from a seven, which gives trips to anyone holding another, while 99 on 955
+// still makes quads with the last nine. Against a known opponent it is an out
+// when it turns a hand that doesn't win into one that does.
+pub fn find_outs(
+    hole_cards: [Card; 2],
+    board: &[Card],
+    opponent: Option<[Card; 2]>,
+) -> Result<Outs, &'static str> {
+    if board.len() != 3 && board.len() != 4 {
+        return Err("Outs need a flop or a turn board");
+    }
+
+    let mut deck = Deck::new();
+    deck.remove(&hole_cards).map_err(|_| "Duplicate card")?;
+    deck.remove(board).map_err(|_| "Duplicate card")?;
+    if let Some(opponent) = opponent {
+        deck.remove(&opponent).map_err(|_| "Duplicate card")?;
+    }
+
+    let value_with = |holding: [Card; 2], next: Option<Card>| {
+        let mut cards: Vec<Card> = holding.iter().chain(board).copied().collect();
+        cards.extend(next);
+        HandValue::from_cards(&cards)
+    };
+    let current = value_with(hole_cards, None);
+    let current_lead = opponent.map(|opponent| current > value_with(opponent, None));
+
+    let mut groups: Vec<(HandType, Vec<Card>)> = Vec::new();
+    for &card in deck.cards() {
+        let value = value_with(hole_cards, Some(card));
+        let is_out = match opponent {
+            Some(opponent) => current_lead == Some(false) && value > value_with(opponent, Some(card)),
+            None => {
+                let mut board_hand = board.to_vec();
+                board_hand.push(card);
+                board_hand.extend(deck.cards().iter().find(|other| other.rank == card.rank && **other != card));
+                value.hand_type() > current.hand_type() && value > current && value > HandValue::from_cards(&board_hand)
+            }
+        };
+        if !is_out {
+            continue;
+        }
+        match groups.iter_mut().find(|(hand_type, _)| *hand_type == value.hand_type()) {
+            Some((_, cards)) => cards.push(card),
+            None => groups.push((value.hand_type(), vec![card])),
+        }
+    }
+    groups.sort_by_key(|&(hand_type, _)| std::cmp::Reverse(hand_type));
+
+    Ok(Outs {
+        groups,
+        unseen: deck.remaining(),
+        cards_to_come: 5 - board.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::{parse_cards, parse_hole_cards};
+
+    fn outs(hole_cards: &str, board: &str, opponent: Option<&str>) -> Outs {
+        let opponent = opponent.map(|opponent| parse_hole_cards(opponent).unwrap());
+        find_outs(parse_hole_cards(hole_cards).unwrap(), &parse_cards(board).unwrap(), opponent).unwrap()
+    }
+
+    #[test]
+    fn test_flush_draw_with_overcards() {
+        // 9 hearts for the flush, 3 aces and 3 kings for a pair; pairing the
+        // board doesn't count
+        let result = outs("AH KH", "2H 7H 9C", None);
+
+        assert_eq!(result.count(), 15);
+        assert_eq!(result.groups[0].0, HandType::Flush);
+        assert_eq!(result.groups[0].1.len(), 9);
+        assert_eq!(result.groups[1], (HandType::OnePair, parse_cards("KD AD KC AC KS AS").unwrap()));
+        assert_eq!(result.unseen, 47);
+        assert!((result.next_card_probability() - 15.0 / 47.0).abs() < 1e-12);
+        assert!((result.by_river_probability() - (1.0 - 496.0 / 1081.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pocket_pair_on_a_board_pair() {
+        // A seven pairs the board: two pair, but the eights gain nothing over
+        // it. Only the two eights left improve the hand.
+        let result = outs("8H 8D", "KC 7S 2D", None);
+        assert_eq!(result.cards(), parse_cards("8C 8S").unwrap());
+        assert_eq!(result.groups[0].0, HandType::ThreeOfAKind);
+
+        // With a set, pairing the board fills up
+        let result = outs("8H 8D", "KC 8S 2D", None);
+        assert_eq!(result.groups[0], (HandType::FourOfAKind, parse_cards("8C").unwrap()));
+        assert_eq!(result.groups[1], (HandType::FullHouse, parse_cards("2H KH KD 2C 2S KS").unwrap()));
+    }
+
+    #[test]
+    fn test_full_house_to_quads() {
+        // The last nine makes quads; a five only makes the same full house
+        let result = outs("9D 9C", "9S 5H 5D", None);
+        assert_eq!(result.groups, vec![(HandType::FourOfAKind, parse_cards("9H").unwrap())]);
+
+        // Only the last ace improves aces full of kings: the kings left give
+        // the same full house
+        let result = outs("AH AD", "AS KH KD", None);
+        assert_eq!(result.groups, vec![(HandType::FourOfAKind, parse_cards("AC").unwrap())]);
+    }
+
+    #[test]
+    fn test_against_an_opponent() {
+        // Overcards and the flush draw against an overpair; the queen of
+        // hearts still gives the better hand
+        let result = outs("AH KH", "2H 7H 9C", Some("QS QD"));
+        assert_eq!(result.count(), 15);
+        assert_eq!(result.unseen, 45);
+
+        // Open-ended straight draw on the turn against top pair
+        let result = outs("8D 7D", "6C 5S KH 2C", Some("KS QS"));
+        assert_eq!(result.cards(), parse_cards("4H 9H 4D 9D 4C 9C 4S 9S").unwrap());
+        assert_eq!(result.groups.len(), 1);
+        assert_eq!(result.groups[0].0, HandType::Straight);
+        assert!((result.by_river_probability() - 8.0 / 44.0).abs() < 1e-12);
+
+        // Nothing to draw to when already ahead
+        assert_eq!(outs("KS QS", "6C 5S KH 2C", Some("8D 7D")).count(), 0);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let hole_cards = parse_hole_cards("AH KH").unwrap();
+        assert!(find_outs(hole_cards, &parse_cards("2H 7H").unwrap(), None).is_err());
+        assert!(find_outs(hole_cards, &parse_cards("2H 7H 9C 3D 4D").unwrap(), None).is_err());
+        assert!(find_outs(hole_cards, &parse_cards("AH 7H 9C").unwrap(), None).is_err());
+        let opponent = parse_hole_cards("KH QS").unwrap();
+        assert!(find_outs(hole_cards, &parse_cards("2H 7H 9C").unwrap(), Some(opponent)).is_err());
+    }
+}
@@ -8,16 +8,30 @@ pub fn parse_hand(hand_str: &str) -> Result<Hand, &'static str> {
         return Err("A hand must contain exactly 5 cards");
     }
     
-    let mut parsed_cards = Vec::with_capacity(5);
+    Hand::new(parse_cards(hand_str)?)
+}
+
+// Parses any number of whitespace-separated cards, e.g. a board or a row
+pub fn parse_cards(cards_str: &str) -> Result<Vec<Card>, &'static str> {
+    let mut parsed_cards = Vec::new();
     
-    for card_str in cards {
-        match parse_card(card_str) {
-            Ok(card) => parsed_cards.push(card),
-            Err(e) => return Err(e),
+    for card_str in cards_str.split_whitespace() {
+        let card = parse_card(card_str)?;
+        if parsed_cards.contains(&card) {
+            return Err("Duplicate card");
         }
+        parsed_cards.push(card);
     }
     
-    Hand::new(parsed_cards)
+    Ok(parsed_cards)
+}
+
+// Parses the two hole cards of a Hold'em player, e.g. "AS KS"
+pub fn parse_hole_cards(cards_str: &str) -> Result<[Card; 2], &'static str> {
+    match parse_cards(cards_str)?[..] {
+        [first, second] => Ok([first, second]),
+        _ => Err("Hole cards must be exactly 2 cards"),
+    }
 }
 
 pub fn parse_card(card_str: &str) -> Result<Card, &'static str> {
@@ -105,4 +119,27 @@ mod tests {
         assert!(parse_hand("AS KS QS JS 10S 9S").is_err());
         assert!(parse_card("AX").is_err());
     }
+    
+    #[test]
+    fn test_parse_cards() {
+        let cards = parse_cards("AS 10D 2C").unwrap();
+        assert_eq!(cards.len(), 3);
+        assert_eq!(cards[1], Card { rank: Rank::Ten, suit: Suit::Diamonds });
+        
+        assert!(parse_cards("").unwrap().is_empty());
+        assert!(parse_cards("AS AS").is_err());
+        assert!(parse_cards("AS 1S").is_err());
+    }
+    
+    #[test]
+    fn test_parse_hole_cards() {
+        assert_eq!(
+            parse_hole_cards("AS 10H").unwrap(),
+            [Card { rank: Rank::Ace, suit: Suit::Spades }, Card { rank: Rank::Ten, suit: Suit::Hearts }]
+        );
+        
+        assert!(parse_hole_cards("AS").is_err());
+        assert!(parse_hole_cards("AS KS QS").is_err());
+        assert!(parse_hole_cards("AS AS").is_err());
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,218 @@
+use super::card::{Card, HandType, Rank, Suit};
+use super::combinations::{binomial, for_each_combination};
+use super::hand::Hand;
+use super::value::HandValue;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+pub const TOTAL_HANDS: u64 = 2_598_960;
+pub const DISTINCT_VALUES: usize = 7_462;
+
+// Number of five-card hands of each type, indexed like `HandType::ALL`
+pub const CATEGORY_HANDS: [u64; 10] =
+    [1_302_540, 1_098_240, 123_552, 54_912, 10_200, 5_108, 3_744, 624, 36, 4];
+
+// Number of distinct hand values of each type, indexed like `HandType::ALL`
+pub const CATEGORY_VALUES: [usize; 10] = [1_277, 2_860, 858, 858, 10, 1_277, 156, 156, 9, 1];
+
+// Where a five-card hand stands among every possible five-card hand, card
+// removal aside
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct HandPercentile {
+    // 1 for a royal flush, 7,462 for 7-5-4-3-2 offsuit
+    pub value_rank: usize,
+    pub better_hands: u64,
+    // Other hands of the same value
+    pub equal_hands: u64,
+    pub worse_hands: u64,
+}
+
+impl HandPercentile {
+    // Share of the other hands this one beats
+    pub fn percentile(&self) -> f64 {
+        self.worse_hands as f64 / (TOTAL_HANDS - 1) as f64
+    }
+}
+
+// Hands and distinct values of each type, as counted by `enumerate_frequencies`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CategoryFrequencies {
+    pub hands: [u64; 10],
+    pub values: [usize; 10],
+}
+
+// Every distinct value from best to worst, with the number of hands making it
+// and the number of hands making a better one
+fn value_table() -> &'static [(HandValue, u64, u64)] {
+    static TABLE: OnceLock<Vec<(HandValue, u64, u64)>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        // Each multiset of ranks is one value off-suit, and when its ranks are
+        // all different one more as a flush; no need to look at every hand
+        let mut values: Vec<(HandValue, u64)> = Vec::with_capacity(DISTINCT_VALUES);
+        let mut counts = [0usize; 13];
+        for_each_rank_multiset(&mut counts, 0, 5, &mut |counts| {
+            let mut cards = Vec::with_capacity(5);
+            for (rank, &count) in Rank::ALL.iter().zip(counts.iter()) {
+                cards.extend(Suit::ALL[..count].iter().map(|&suit| Card { rank: *rank, suit }));
+            }
+            let hands: u64 = counts.iter().map(|&count| binomial(4, count)).product();
+
+            if counts.iter().all(|&count| count <= 1) {
+                cards[0].suit = Suit::Diamonds;
+                values.push((HandValue::from_cards(&cards), hands - 4));
+                for card in cards.iter_mut() {
+                    card.suit = Suit::Hearts;
+                }
+                values.push((HandValue::from_cards(&cards), 4));
+            } else {
+                values.push((HandValue::from_cards(&cards), hands));
+            }
+        });
+
+        values.sort_by_key(|&(value, _)| std::cmp::Reverse(value));
+        let mut better = 0;
+        values
+            .into_iter()
+            .map(|(value, hands)| {
+                let entry = (value, hands, better);
+                better += hands;
+                entry
+            })
+            .collect()
+    })
+}
+
+// Calls `visit` with every way to pick `left` more ranks from `rank` up, at
+// most four of each
+fn for_each_rank_multiset(
+    counts: &mut [usize; 13],
+    rank: usize,
+    left: usize,
+    visit: &mut impl FnMut(&[usize; 13]),
+) {
+    if left == 0 {
+        visit(counts);
+        return;
+    }
+    if rank == 13 {
+        return;
+    }
+    for count in (0..=left.min(4)).rev() {
+        counts[rank] = count;
+        for_each_rank_multiset(counts, rank + 1, left - count, visit);
+    }
+    counts[rank] = 0;
+}
+
+// Exact standing of a five-card hand among the 7,462 distinct values and the
+// 2,598,960 possible hands
+pub fn hand_percentile(hand: &Hand) -> Result<HandPercentile, &'static str> {
+    if hand.cards.len() != 5 {
+        return Err("A hand must contain exactly 5 cards");
+    }
+
+    let value = hand.value();
+    let table = value_table();
+    let index = table
+        .binary_search_by(|&(other, _, _)| value.cmp(&other))
+        .map_err(|_| "Not a five-card hand value")?;
+    let (_, hands, better) = table[index];
+
+    Ok(HandPercentile {
+        value_rank: index + 1,
+        better_hands: better,
+        equal_hands: hands - 1,
+        worse_hands: TOTAL_HANDS - better - hands,
+    })
+}
+
+// Counts hands and distinct values of each type by evaluating all 2,598,960
+// hands, to check the precomputed tables against
+pub fn enumerate_frequencies() -> CategoryFrequencies {
+    let deck: Vec<Card> = (0..52).map(Card::from_index).collect();
+    let mut hands = [0u64; 10];
+    let mut distinct: HashMap<HandValue, u64> = HashMap::new();
+
+    for_each_combination(&deck, 5, |cards| {
+        let value = HandValue::from_cards(cards);
+        hands[value.hand_type() as usize] += 1;
+        *distinct.entry(value).or_insert(0) += 1;
+    });
+
+    let mut values = [0usize; 10];
+    for value in distinct.keys() {
+        values[value.hand_type() as usize] += 1;
+    }
+    CategoryFrequencies { hands, values }
+}
+
+// Probability that a random five-card hand is of the given type
+pub fn category_probability(hand_type: HandType) -> f64 {
+    CATEGORY_HANDS[hand_type as usize] as f64 / TOTAL_HANDS as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::parse_hand;
+
+    fn percentile(hand: &str) -> HandPercentile {
+        hand_percentile(&parse_hand(hand).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_tables_match_enumeration() {
+        let enumerated = enumerate_frequencies();
+        assert_eq!(enumerated.hands, CATEGORY_HANDS);
+        assert_eq!(enumerated.values, CATEGORY_VALUES);
+        assert_eq!(CATEGORY_HANDS.iter().sum::<u64>(), TOTAL_HANDS);
+        assert_eq!(CATEGORY_VALUES.iter().sum::<usize>(), DISTINCT_VALUES);
+
+        // The value table agrees as well, category by category
+        let table = value_table();
+        assert_eq!(table.len(), DISTINCT_VALUES);
+        let mut hands = [0u64; 10];
+        for &(value, count, _) in table {
+            hands[value.hand_type() as usize] += count;
+        }
+        assert_eq!(hands, CATEGORY_HANDS);
+    }
+
+    #[test]
+    fn test_extremes() {
+        let royal = percentile("AS KS QS JS 10S");
+        let expected = HandPercentile { value_rank: 1, better_hands: 0, equal_hands: 3, worse_hands: 2_598_956 };
+        assert_eq!(royal, expected);
+
+        let worst = percentile("7S 5H 4D 3C 2S");
+        assert_eq!(worst.value_rank, DISTINCT_VALUES);
+        assert_eq!(worst.equal_hands, 1019);
+        assert_eq!(worst.worse_hands, 0);
+        assert_eq!(worst.percentile(), 0.0);
+    }
+
+    #[test]
+    fn test_known_ranks() {
+        // Four aces with a king are beaten only by the 40 straight flushes
+        let quads = percentile("AS AH AD AC KS");
+        assert_eq!(quads.value_rank, 11);
+        assert_eq!(quads.better_hands, 40);
+        assert_eq!(quads.equal_hands, 3);
+
+        // The lowest full house comes after every other one
+        let boat = percentile("2S 2H 2D 3C 3S");
+        assert_eq!(boat.value_rank, 1 + 9 + 156 + 156);
+        assert_eq!(boat.worse_hands, TOTAL_HANDS - 40 - 624 - 3_744);
+
+        // The wheel is the lowest straight
+        let wheel = percentile("AS 2H 3D 4C 5S");
+        assert_eq!(wheel.better_hands, 40 + 624 + 3_744 + 5_108 + 10_200 - 1_020);
+        assert!((percentile("KS KH 7D 4C 2S").percentile() - 0.8).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_category_probability() {
+        assert!((category_probability(HandType::OnePair) - 0.422569).abs() < 1e-6);
+        assert!((category_probability(HandType::RoyalFlush) - 1.539e-6).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,240 @@
+use super::card::Card;
+use super::equity::{showdown_values, Tally};
+use super::range::HandClass;
+use super::rng::Rng;
+use super::simulation::Simulation;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// File layout: magic, version, trials per matchup (u32), then every equity as
+// a little-endian u16 in 1/65534ths (even, so an even split is exact): the
+// 169x169 matchups row by row in grid order, followed by the 169 equities
+// against a random hand
+const MAGIC: &[u8; 4] = b"PFEQ";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 9;
+const CLASSES: usize = 169;
+const SCALE: f64 = 65534.0;
+
+// Heads-up all-in equity between the 169 starting-hand classes, estimated by
+// dealing random combos of both classes and random boards
+#[derive(Debug, PartialEq, Clone)]
+pub struct PreflopTable {
+    trials: u32,
+    matchups: Vec<u16>,
+    vs_random: Vec<u16>,
+}
+
+impl PreflopTable {
+    // Runs `trials` deals per matchup on `threads` threads. Every matchup is a
+    // job of its own, so the table only depends on the seed and the number of
+    // trials, not on how the work is split.
+    pub fn generate(trials: u32, seed: u64, threads: usize) -> Result<Self, &'static str> {
+        if trials == 0 {
+            return Err("At least one trial is needed");
+        }
+        if threads == 0 {
+            return Err("At least one thread is needed");
+        }
+
+        // Each unordered pair of different classes once, then every class
+        // against a random hand
+        let mut jobs: Vec<(usize, Option<usize>)> = Vec::new();
+        for hero in 0..CLASSES {
+            jobs.extend((hero + 1..CLASSES).map(|villain| (hero, Some(villain))));
+        }
+        jobs.extend((0..CLASSES).map(|hero| (hero, None)));
+
+        let simulation = Simulation::new(seed).with_threads(threads);
+        let equities = simulation.map(jobs.len(), |job, rng| {
+            let (hero, villain) = jobs[job];
+            simulate(HandClass::from_grid_index(hero), villain.map(HandClass::from_grid_index), trials, rng)
+        });
+
+        let mut table = PreflopTable {
+            trials,
+            matchups: vec![quantize(0.5); CLASSES * CLASSES],
+            vs_random: vec![0; CLASSES],
+        };
+        for (job, equity) in equities.into_iter().enumerate() {
+            match jobs[job] {
+                (hero, Some(villain)) => {
+                    table.matchups[hero * CLASSES + villain] = quantize(equity);
+                    table.matchups[villain * CLASSES + hero] = quantize(1.0 - equity);
+                }
+                (hero, None) => table.vs_random[hero] = quantize(equity),
+            }
+        }
+        Ok(table)
+    }
+
+    pub fn trials(&self) -> u32 {
+        self.trials
+    }
+
+    // Equity of `hero` all-in against `villain`; a class against itself is an
+    // even split
+    pub fn equity(&self, hero: HandClass, villain: HandClass) -> f64 {
+        self.matchups[hero.grid_index() * CLASSES + villain.grid_index()] as f64 / SCALE
+    }
+
+    pub fn equity_vs_random(&self, hero: HandClass) -> f64 {
+        self.vs_random[hero.grid_index()] as f64 / SCALE
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + 2 * (CLASSES * CLASSES + CLASSES));
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.trials.to_le_bytes());
+        for equity in self.matchups.iter().chain(&self.vs_random) {
+            bytes.extend_from_slice(&equity.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+            return Err("Not a pre-flop equity table");
+        }
+        if bytes[4] != VERSION {
+            return Err("Unsupported pre-flop equity table version");
+        }
+        if bytes.len() != HEADER_LEN + 2 * (CLASSES * CLASSES + CLASSES) {
+            return Err("Truncated pre-flop equity table");
+        }
+
+        let trials = u32::from_le_bytes(bytes[5..9].try_into().unwrap());
+        let mut equities: Vec<u16> = bytes[HEADER_LEN..]
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        let vs_random = equities.split_off(CLASSES * CLASSES);
+        Ok(PreflopTable { trials, matchups: equities, vs_random })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        PreflopTable::from_bytes(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+fn quantize(equity: f64) -> u16 {
+    (equity * SCALE).round() as u16
+}
+
+// Draws a card that isn't in `used`, and marks it as used
+fn draw_card(rng: &mut Rng, used: &mut u64) -> Card {
+    loop {
+        let index = rng.below(52);
+        if *used & (1 << index) == 0 {
+            *used |= 1 << index;
+            return Card::from_index(index);
+        }
+    }
+}
+
+// Equity of the hero class against the villain class, or against any two
+// cards when there is no villain class
+fn simulate(hero: HandClass, villain: Option<HandClass>, trials: u32, rng: &mut Rng) -> f64 {
+    let hero_combos = hero.combos();
+    let villain_combos = villain.map(|villain| villain.combos());
+    let mask = |combo: [Card; 2]| (1u64 << combo[0].index()) | (1u64 << combo[1].index());
+
+    let mut tally = Tally::new(2);
+    let mut values = Vec::with_capacity(2);
+    let mut board = [hero_combos[0][0]; 5];
+
+    for _ in 0..trials {
+        let (holdings, mut used) = loop {
+            let hero = hero_combos[rng.below(hero_combos.len())];
+            let mut used = mask(hero);
+            let villain = match &villain_combos {
+                Some(combos) => {
+                    let villain = combos[rng.below(combos.len())];
+                    if used & mask(villain) != 0 {
+                        continue;
+                    }
+                    used |= mask(villain);
+                    villain
+                }
+                None => [draw_card(rng, &mut used), draw_card(rng, &mut used)],
+            };
+            break ([hero, villain], used);
+        };
+
+        for card in board.iter_mut() {
+            *card = draw_card(rng, &mut used);
+        }
+        showdown_values(&holdings, &board, &mut values);
+        tally.record(&values);
+    }
+
+    tally.result(false).players[0].equity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::card::Rank;
+
+    fn class(first: Rank, second: Rank, suited: bool) -> HandClass {
+        HandClass::new(first, second, suited)
+    }
+
+    #[test]
+    fn test_generation_is_deterministic() {
+        let single = PreflopTable::generate(40, 7, 1).unwrap();
+        let several = PreflopTable::generate(40, 7, 5).unwrap();
+        assert_eq!(single, several);
+        assert_ne!(single, PreflopTable::generate(40, 8, 5).unwrap());
+    }
+
+    #[test]
+    fn test_known_equities() {
+        let table = PreflopTable::generate(1_000, 1, 4).unwrap();
+        let aces = class(Rank::Ace, Rank::Ace, false);
+        let kings = class(Rank::King, Rank::King, false);
+        let ace_king = class(Rank::Ace, Rank::King, true);
+        let seven_deuce = class(Rank::Seven, Rank::Two, false);
+
+        assert_eq!(table.trials(), 1_000);
+        assert!((table.equity(aces, kings) - 0.82).abs() < 0.06);
+        assert!((table.equity(aces, kings) + table.equity(kings, aces) - 1.0).abs() < 1e-4);
+        assert_eq!(table.equity(ace_king, ace_king), 0.5);
+        assert!((table.equity_vs_random(aces) - 0.85).abs() < 0.05);
+        assert!((table.equity_vs_random(seven_deuce) - 0.35).abs() < 0.05);
+        assert!(table.equity_vs_random(aces) > table.equity_vs_random(kings));
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let table = PreflopTable::generate(10, 3, 2).unwrap();
+        let bytes = table.to_bytes();
+        assert_eq!(bytes.len(), 9 + 2 * (169 * 169 + 169));
+        assert_eq!(PreflopTable::from_bytes(&bytes).unwrap(), table);
+
+        let path = std::env::temp_dir().join(format!("preflop-table-test-{}.bin", std::process::id()));
+        table.save(&path).unwrap();
+        let loaded = PreflopTable::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), table);
+
+        assert!(PreflopTable::from_bytes(&bytes[..100]).is_err());
+        assert!(PreflopTable::from_bytes(b"not a table").is_err());
+        let mut other_version = bytes.clone();
+        other_version[4] = 2;
+        assert!(PreflopTable::from_bytes(&other_version).is_err());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(PreflopTable::generate(0, 1, 1).is_err());
+        assert!(PreflopTable::generate(10, 1, 0).is_err());
+    }
+}
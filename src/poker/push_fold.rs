@@ -0,0 +1,278 @@
+use super::preflop::PreflopTable;
+use super::range::HandClass;
+use std::fmt::Write;
+
+const CLASSES: usize = 169;
+
+// Rounds of fictitious play; the average strategies get within a few
+// hundredths of a big blind of the equilibrium
+const ITERATIONS: usize = 2_000;
+
+// Heads-up push/fold equilibrium: the small blind shoves or folds, the big
+// blind calls or folds. Frequencies are indexed by grid position and amounts
+// are in big blinds.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PushFoldSolution {
+    pub stack: f64,
+    pub ante: f64,
+    pub shove: Vec<f64>,
+    pub call: Vec<f64>,
+    // What the small blind makes per hand, counted from before the blinds
+    pub shover_ev: f64,
+    // What the two players could gain together by deviating; 0 at an exact
+    // equilibrium
+    pub exploitability: f64,
+}
+
+// The two players' payoffs, from the small blind's side (the game is zero-sum)
+struct Game {
+    ante: f64,
+    // Combos of each class compatible with one combo of another
+    weights: Vec<[f64; CLASSES]>,
+    // Showdown value of an all-in for the first class, in big blinds
+    showdown: Vec<[f64; CLASSES]>,
+}
+
+impl Game {
+    fn new(table: &PreflopTable, stack: f64, ante: f64) -> Self {
+        let classes = HandClass::all();
+        let mut weights = vec![[0.0; CLASSES]; CLASSES];
+        let mut showdown = vec![[0.0; CLASSES]; CLASSES];
+        for hero in &classes {
+            // Every combo of a class sees the others the same way
+            let combo = hero.combos()[0];
+            for villain in &classes {
+                let (row, column) = (hero.grid_index(), villain.grid_index());
+                weights[row][column] = villain
+                    .combos()
+                    .iter()
+                    .filter(|other| !other.iter().any(|card| combo.contains(card)))
+                    .count() as f64;
+                showdown[row][column] = 2.0 * stack * table.equity(*hero, *villain) - stack;
+            }
+        }
+        Game { ante, weights, showdown }
+    }
+
+    fn fold_ev(&self) -> f64 {
+        -(0.5 + self.ante)
+    }
+
+    fn steal_ev(&self) -> f64 {
+        1.0 + self.ante
+    }
+
+    // Small blind's EV of shoving each class against a calling strategy
+    fn shove_evs(&self, call: &[f64]) -> Vec<f64> {
+        (0..CLASSES)
+            .map(|hero| {
+                let (mut total, mut value) = (0.0, 0.0);
+                for (villain, &call) in call.iter().enumerate() {
+                    let weight = self.weights[hero][villain];
+                    total += weight;
+                    value += weight * ((1.0 - call) * self.steal_ev() + call * self.showdown[hero][villain]);
+                }
+                value / total
+            })
+            .collect()
+    }
+
+    // Big blind's EV of calling with each class against a shoving strategy,
+    // None when no shove can come with that class
+    fn call_evs(&self, shove: &[f64]) -> Vec<Option<f64>> {
+        (0..CLASSES)
+            .map(|villain| {
+                let (mut total, mut value) = (0.0, 0.0);
+                for (hero, &shove) in shove.iter().enumerate() {
+                    let weight = self.weights[villain][hero] * shove;
+                    total += weight;
+                    value += weight * self.showdown[villain][hero];
+                }
+                (total > 0.0).then(|| value / total)
+            })
+            .collect()
+    }
+
+    fn best_shove(&self, call: &[f64]) -> Vec<f64> {
+        let fold = self.fold_ev();
+        self.shove_evs(call).into_iter().map(|ev| if ev > fold { 1.0 } else { 0.0 }).collect()
+    }
+
+    fn best_call(&self, shove: &[f64]) -> Vec<f64> {
+        let fold = -(1.0 + self.ante);
+        self.call_evs(shove)
+            .into_iter()
+            .map(|ev| match ev {
+                Some(ev) if ev > fold => 1.0,
+                _ => 0.0,
+            })
+            .collect()
+    }
+
+    // Small blind's EV per hand when both strategies are played
+    fn value(&self, shove: &[f64], call: &[f64]) -> f64 {
+        let evs = self.shove_evs(call);
+        HandClass::all()
+            .iter()
+            .map(|class| {
+                let index = class.grid_index();
+                let share = class.combo_count() as f64 / 1326.0;
+                share * (shove[index] * evs[index] + (1.0 - shove[index]) * self.fold_ev())
+            })
+            .sum()
+    }
+}
+
+// Solves heads-up push/fold for an effective stack in big blinds, blinds and
+// antes included, with an ante per player, by fictitious play on the class
+// equities of `table`
+pub fn solve_heads_up(table: &PreflopTable, stack: f64, ante: f64) -> Result<PushFoldSolution, &'static str> {
+    if !(ante.is_finite() && ante >= 0.0) {
+        return Err("The ante can't be negative");
+    }
+    if !(stack.is_finite() && stack >= 1.0 + ante) {
+        return Err("The stack must cover the big blind and the ante");
+    }
+
+    let game = Game::new(table, stack, ante);
+    let mut shove = vec![1.0; CLASSES];
+    let mut call = vec![1.0; CLASSES];
+    for round in 1..=ITERATIONS {
+        let best_shove = game.best_shove(&call);
+        let best_call = game.best_call(&shove);
+        let step = 1.0 / (round + 1) as f64;
+        for (average, best) in shove.iter_mut().zip(best_shove) {
+            *average += (best - *average) * step;
+        }
+        for (average, best) in call.iter_mut().zip(best_call) {
+            *average += (best - *average) * step;
+        }
+    }
+
+    let shover_ev = game.value(&shove, &call);
+    let exploitability =
+        game.value(&game.best_shove(&call), &call) - game.value(&shove, &game.best_call(&shove));
+    Ok(PushFoldSolution { stack, ante, shove, call, shover_ev, exploitability })
+}
+
+impl PushFoldSolution {
+    pub fn shove_frequency(&self, class: HandClass) -> f64 {
+        self.shove[class.grid_index()]
+    }
+
+    pub fn call_frequency(&self, class: HandClass) -> f64 {
+        self.call[class.grid_index()]
+    }
+
+    // Share of all 1,326 combos shoved
+    pub fn shove_percentage(&self) -> f64 {
+        combo_share(&self.shove)
+    }
+
+    pub fn call_percentage(&self) -> f64 {
+        combo_share(&self.call)
+    }
+
+    pub fn shove_chart(&self) -> String {
+        chart(&self.shove)
+    }
+
+    pub fn call_chart(&self) -> String {
+        chart(&self.call)
+    }
+}
+
+fn combo_share(frequencies: &[f64]) -> f64 {
+    HandClass::all()
+        .iter()
+        .map(|class| frequencies[class.grid_index()] * class.combo_count() as f64)
+        .sum::<f64>()
+        / 1326.0
+}
+
+// 13x13 grid, pairs on the diagonal and suited hands above it: the class name
+// where it's (nearly) always played, "." where it (nearly) never is, the
+// frequency otherwise; fictitious play leaves a few percent of noise
+fn chart(frequencies: &[f64]) -> String {
+    let mut chart = String::new();
+    for row in 0..13 {
+        let cells: Vec<String> = (0..13)
+            .map(|column| {
+                let index = row * 13 + column;
+                match frequencies[index] {
+                    frequency if frequency >= 0.95 => HandClass::from_grid_index(index).to_string(),
+                    frequency if frequency <= 0.05 => ".".to_string(),
+                    frequency => format!("{:.0}%", frequency * 100.0),
+                }
+            })
+            .collect();
+        let line: Vec<String> = cells.iter().map(|cell| format!("{:>4}", cell)).collect();
+        let _ = writeln!(chart, "{}", line.join(""));
+    }
+    chart
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::card::Rank;
+    use std::sync::OnceLock;
+
+    fn table() -> &'static PreflopTable {
+        static TABLE: OnceLock<PreflopTable> = OnceLock::new();
+        TABLE.get_or_init(|| PreflopTable::generate(300, 1, 4).unwrap())
+    }
+
+    fn class(first: Rank, second: Rank, suited: bool) -> HandClass {
+        HandClass::new(first, second, suited)
+    }
+
+    #[test]
+    fn test_ten_big_blinds() {
+        let solution = solve_heads_up(table(), 10.0, 0.0).unwrap();
+        assert!(solution.exploitability < 0.05);
+        assert!(solution.exploitability >= -1e-9);
+
+        let aces = class(Rank::Ace, Rank::Ace, false);
+        assert_eq!(solution.shove_frequency(aces), 1.0);
+        assert_eq!(solution.call_frequency(aces), 1.0);
+        assert!(solution.call_frequency(class(Rank::Seven, Rank::Two, false)) < 0.01);
+
+        // The small blind shoves a wide range, the big blind calls tighter
+        assert!(solution.shove_percentage() > 0.45 && solution.shove_percentage() < 0.75);
+        assert!(solution.call_percentage() > 0.25 && solution.call_percentage() < solution.shove_percentage());
+        assert!(solution.shover_ev > -0.5);
+    }
+
+    #[test]
+    fn test_stack_depth_and_antes() {
+        let short = solve_heads_up(table(), 2.0, 0.0).unwrap();
+        let deep = solve_heads_up(table(), 25.0, 0.0).unwrap();
+        assert!(short.shove_percentage() > 0.85);
+        assert!(deep.shove_percentage() < short.shove_percentage());
+
+        // Antes make the blinds worth more, so both play looser
+        let antes = solve_heads_up(table(), 25.0, 0.25).unwrap();
+        assert!(antes.shove_percentage() > deep.shove_percentage());
+        assert!(antes.call_percentage() > deep.call_percentage());
+    }
+
+    #[test]
+    fn test_chart() {
+        let solution = solve_heads_up(table(), 10.0, 0.0).unwrap();
+        let chart = solution.shove_chart();
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert!(lines[0].starts_with("  AA AKs AQs"));
+        assert!(lines.iter().all(|line| line.len() == 52));
+        assert!(lines[12].starts_with(" A2o"));
+        assert!(solution.call_chart().lines().nth(12).unwrap().ends_with("  22"));
+        assert!(solution.call_chart().lines().nth(12).unwrap().contains("   ."));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(solve_heads_up(table(), 0.5, 0.0).is_err());
+        assert!(solve_heads_up(table(), 10.0, -1.0).is_err());
+    }
+}
@@ -0,0 +1,656 @@
+use super::card::{Card, Rank, Suit};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum HandClassKind {
+    Pair,
+    Suited,
+    Offsuit,
+}
+
+// One of the 169 strategically distinct Hold'em starting hands, e.g. AKs
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct HandClass {
+    pub high: Rank,
+    pub low: Rank,
+    pub kind: HandClassKind,
+}
+
+impl HandClass {
+    pub fn new(first: Rank, second: Rank, suited: bool) -> Self {
+        let (high, low) = if first >= second { (first, second) } else { (second, first) };
+        let kind = if high == low {
+            HandClassKind::Pair
+        } else if suited {
+            HandClassKind::Suited
+        } else {
+            HandClassKind::Offsuit
+        };
+        HandClass { high, low, kind }
+    }
+
+    pub fn of(hole_cards: [Card; 2]) -> Self {
+        let [first, second] = hole_cards;
+        HandClass::new(first.rank, second.rank, first.suit == second.suit)
+    }
+
+    // A single class such as "AKs", "AKo" or "TT"
+    pub fn parse(text: &str) -> Result<Self, &'static str> {
+        match parse_class_pattern(text.trim())? {
+            (high, low, kinds) if kinds.len() == 1 => Ok(HandClass { high, low, kind: kinds[0] }),
+            _ => Err("A hand class must say whether it is suited or offsuit"),
+        }
+    }
+
+    // Every class in the usual 13x13 grid order: aces first, pairs on the
+    // diagonal, suited hands above it and offsuit hands below it
+    pub fn all() -> Vec<HandClass> {
+        (0..169).map(HandClass::from_grid_index).collect()
+    }
+
+    pub fn grid_index(&self) -> usize {
+        let high = 14 - self.high as usize;
+        let low = 14 - self.low as usize;
+        match self.kind {
+            HandClassKind::Offsuit => low * 13 + high,
+            _ => high * 13 + low,
+        }
+    }
+
+    pub fn from_grid_index(index: usize) -> Self {
+        let row = Rank::ALL[12 - index / 13];
+        let column = Rank::ALL[12 - index % 13];
+        HandClass::new(row, column, row > column)
+    }
+
+    pub fn combo_count(&self) -> usize {
+        match self.kind {
+            HandClassKind::Pair => 6,
+            HandClassKind::Suited => 4,
+            HandClassKind::Offsuit => 12,
+        }
+    }
+
+    // Concrete two-card holdings of the class, higher card first
+    pub fn combos(&self) -> Vec<[Card; 2]> {
+        let mut combos = Vec::with_capacity(self.combo_count());
+        for (i, &first) in Suit::ALL.iter().enumerate() {
+            for (j, &second) in Suit::ALL.iter().enumerate() {
+                let keep = match self.kind {
+                    HandClassKind::Pair => i < j,
+                    HandClassKind::Suited => i == j,
+                    HandClassKind::Offsuit => i != j,
+                };
+                if keep {
+                    combos.push([
+                        Card { rank: self.high, suit: first },
+                        Card { rank: self.low, suit: second },
+                    ]);
+                }
+            }
+        }
+        combos
+    }
+}
+
+impl fmt::Display for HandClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let suffix = match self.kind {
+            HandClassKind::Pair => "",
+            HandClassKind::Suited => "s",
+            HandClassKind::Offsuit => "o",
+        };
+        write!(f, "{}{}{}", rank_char(self.high), rank_char(self.low), suffix)
+    }
+}
+
+// A weighted set of two-card holdings, as in "TT+, AKs, A5s-A2s:0.5"
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Range {
+    // Keyed by the card indexes of the combo, lowest first
+    combos: BTreeMap<(usize, usize), f64>,
+}
+
+impl Range {
+    pub fn new() -> Self {
+        Range::default()
+    }
+
+    // Adds a combo, replacing its weight if it was already in the range
+    pub fn add_combo(&mut self, combo: [Card; 2], weight: f64) {
+        let (a, b) = (combo[0].index(), combo[1].index());
+        if a != b {
+            self.combos.insert((a.min(b), a.max(b)), weight);
+        }
+    }
+
+    pub fn add_class(&mut self, class: HandClass, weight: f64) {
+        for combo in class.combos() {
+            self.add_combo(combo, weight);
+        }
+    }
+
+    // Weight of a combo, zero if it isn't in the range
+    pub fn weight(&self, combo: [Card; 2]) -> f64 {
+        let (a, b) = (combo[0].index(), combo[1].index());
+        self.combos.get(&(a.min(b), a.max(b))).copied().unwrap_or(0.0)
+    }
+
+    // Every combo with its weight, higher card first
+    pub fn combos(&self) -> Vec<([Card; 2], f64)> {
+        self.combos
+            .iter()
+            .map(|(&(a, b), &weight)| (ordered_combo(a, b), weight))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    // Number of combos counted by weight
+    pub fn total_weight(&self) -> f64 {
+        self.combos.values().sum()
+    }
+
+    // Same range without the combos using any of the given cards
+    pub fn without(&self, dead: &[Card]) -> Range {
+        let dead: Vec<usize> = dead.iter().map(|card| card.index()).collect();
+        Range {
+            combos: self
+                .combos
+                .iter()
+                .filter(|((a, b), _)| !dead.contains(a) && !dead.contains(b))
+                .map(|(&key, &weight)| (key, weight))
+                .collect(),
+        }
+    }
+}
+
+// Lists pairs first, then connectors climbing to the ace, then each high
+// card from aces down, compressing runs of complete classes with the same
+// weight; leftover combos come last.
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Weight shared by every combo of each complete class, by grid index
+        let mut class_weights = [None; 169];
+        let mut loose = Vec::new();
+        for class in HandClass::all() {
+            let weights: Vec<([Card; 2], f64)> =
+                class.combos().into_iter().map(|combo| (combo, self.weight(combo))).collect();
+            let first = weights[0].1;
+            if first > 0.0 && weights.iter().all(|&(_, weight)| weight == first) {
+                class_weights[class.grid_index()] = Some(first.to_bits());
+            } else {
+                loose.extend(weights.into_iter().filter(|&(_, weight)| weight > 0.0));
+            }
+        }
+        let descending: Vec<Rank> = Rank::ALL.iter().rev().copied().collect();
+
+        // Connectors from AK down sharing a weight, written "76s+" when there
+        // are at least two. A class they cover stays in its row only when the
+        // next kicker down carries the row's run on.
+        let mut connectors = Vec::new();
+        for kind in [HandClassKind::Suited, HandClassKind::Offsuit] {
+            let weights: Vec<Option<u64>> = descending
+                .windows(2)
+                .map(|pair| class_weights[HandClass { high: pair[0], low: pair[1], kind }.grid_index()])
+                .collect();
+            let (last, weight) = match runs(&weights).first() {
+                Some(&(0, last, weight)) if last > 0 => (last, weight),
+                _ => continue,
+            };
+            for i in 0..=last {
+                let row_goes_on = descending.get(i + 2).is_some_and(|&next| {
+                    class_weights[HandClass { high: descending[i], low: next, kind }.grid_index()] == Some(weight)
+                });
+                if !row_goes_on {
+                    class_weights[HandClass { high: descending[i], low: descending[i + 1], kind }.grid_index()] = None;
+                }
+            }
+            connectors.push((kind, last, weight));
+        }
+
+        let weight_of = |high: Rank, low: Rank, kind: HandClassKind| {
+            class_weights[HandClass { high, low, kind }.grid_index()]
+        };
+
+        let mut parts = Vec::new();
+
+        let pairs: Vec<Option<u64>> = descending
+            .iter()
+            .map(|&rank| weight_of(rank, rank, HandClassKind::Pair))
+            .collect();
+        for (first, last, weight) in runs(&pairs) {
+            let (top, bottom) = (rank_char(descending[first]), rank_char(descending[last]));
+            let text = if first == last {
+                format!("{}{}", top, top)
+            } else if first == 0 {
+                format!("{}{}+", bottom, bottom)
+            } else {
+                format!("{}{}-{}{}", top, top, bottom, bottom)
+            };
+            parts.push(with_weight(text, weight));
+        }
+
+        let connector = |last: usize, suffix: &str| {
+            format!("{}{}{}+", rank_char(descending[last]), rank_char(descending[last + 1]), suffix)
+        };
+        match connectors[..] {
+            [(_, suited, a), (_, offsuit, b)] if suited == offsuit && a == b => {
+                parts.push(with_weight(connector(suited, ""), a));
+            }
+            _ => {
+                for &(kind, last, weight) in &connectors {
+                    let suffix = if kind == HandClassKind::Suited { "s" } else { "o" };
+                    parts.push(with_weight(connector(last, suffix), weight));
+                }
+            }
+        }
+
+        for (i, &high) in descending.iter().enumerate() {
+            let kickers = &descending[i + 1..];
+            let suited: Vec<Option<u64>> =
+                kickers.iter().map(|&low| weight_of(high, low, HandClassKind::Suited)).collect();
+            let offsuit: Vec<Option<u64>> =
+                kickers.iter().map(|&low| weight_of(high, low, HandClassKind::Offsuit)).collect();
+
+            // Suited and offsuit classes with the same weight are written together
+            let both: Vec<Option<u64>> = suited
+                .iter()
+                .zip(&offsuit)
+                .map(|(&s, &o)| if s == o { s } else { None })
+                .collect();
+            let only = |weights: &[Option<u64>]| -> Vec<Option<u64>> {
+                weights
+                    .iter()
+                    .zip(&both)
+                    .map(|(&weight, shared)| if shared.is_some() { None } else { weight })
+                    .collect()
+            };
+
+            for (weights, suffix) in [(both.clone(), ""), (only(&suited), "s"), (only(&offsuit), "o")] {
+                for (first, last, weight) in runs(&weights) {
+                    let h = rank_char(high);
+                    let (top, bottom) = (rank_char(kickers[first]), rank_char(kickers[last]));
+                    let text = if first == last {
+                        format!("{}{}{}", h, top, suffix)
+                    } else if first == 0 {
+                        format!("{}{}{}+", h, bottom, suffix)
+                    } else {
+                        format!("{}{}{}-{}{}{}", h, top, suffix, h, bottom, suffix)
+                    };
+                    parts.push(with_weight(text, weight));
+                }
+            }
+        }
+
+        for (combo, weight) in loose {
+            let text = format!(
+                "{}{}{}{}",
+                rank_char(combo[0].rank),
+                suit_char(combo[0].suit),
+                rank_char(combo[1].rank),
+                suit_char(combo[1].suit)
+            );
+            parts.push(with_weight(text, weight.to_bits()));
+        }
+
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+// Maximal runs of consecutive entries sharing the same weight, as
+// (first position, last position, weight)
+fn runs(weights: &[Option<u64>]) -> Vec<(usize, usize, u64)> {
+    let mut runs = Vec::new();
+    let mut i = 0;
+    while i < weights.len() {
+        if let Some(weight) = weights[i] {
+            let mut last = i;
+            while last + 1 < weights.len() && weights[last + 1] == Some(weight) {
+                last += 1;
+            }
+            runs.push((i, last, weight));
+            i = last;
+        }
+        i += 1;
+    }
+    runs
+}
+
+fn with_weight(text: String, weight_bits: u64) -> String {
+    let weight = f64::from_bits(weight_bits);
+    if weight == 1.0 {
+        text
+    } else {
+        format!("{}:{}", text, weight)
+    }
+}
+
+fn ordered_combo(a: usize, b: usize) -> [Card; 2] {
+    let (first, second) = (Card::from_index(a), Card::from_index(b));
+    if (second.rank, second.suit as usize) > (first.rank, first.suit as usize) {
+        [second, first]
+    } else {
+        [first, second]
+    }
+}
+
+fn rank_char(rank: Rank) -> char {
+    match rank {
+        Rank::Ten => 'T',
+        Rank::Jack => 'J',
+        Rank::Queen => 'Q',
+        Rank::King => 'K',
+        Rank::Ace => 'A',
+        rank => (b'0' + rank as u8) as char,
+    }
+}
+
+fn suit_char(suit: Suit) -> char {
+    match suit {
+        Suit::Hearts => 'h',
+        Suit::Diamonds => 'd',
+        Suit::Clubs => 'c',
+        Suit::Spades => 's',
+    }
+}
+
+fn parse_rank_char(c: char) -> Result<Rank, &'static str> {
+    match c.to_ascii_uppercase() {
+        '2' => Ok(Rank::Two),
+        '3' => Ok(Rank::Three),
+        '4' => Ok(Rank::Four),
+        '5' => Ok(Rank::Five),
+        '6' => Ok(Rank::Six),
+        '7' => Ok(Rank::Seven),
+        '8' => Ok(Rank::Eight),
+        '9' => Ok(Rank::Nine),
+        'T' => Ok(Rank::Ten),
+        'J' => Ok(Rank::Jack),
+        'Q' => Ok(Rank::Queen),
+        'K' => Ok(Rank::King),
+        'A' => Ok(Rank::Ace),
+        _ => Err("Invalid rank"),
+    }
+}
+
+fn parse_suit_char(c: char) -> Result<Suit, &'static str> {
+    match c.to_ascii_lowercase() {
+        'h' => Ok(Suit::Hearts),
+        'd' => Ok(Suit::Diamonds),
+        'c' => Ok(Suit::Clubs),
+        's' => Ok(Suit::Spades),
+        _ => Err("Invalid suit"),
+    }
+}
+
+// Ranks and kinds written in a single range item, e.g. "AKs" or "TT"
+fn parse_class_pattern(text: &str) -> Result<(Rank, Rank, Vec<HandClassKind>), &'static str> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() < 2 || chars.len() > 3 {
+        return Err("Invalid hand class");
+    }
+
+    let first = parse_rank_char(chars[0])?;
+    let second = parse_rank_char(chars[1])?;
+    let (high, low) = if first >= second { (first, second) } else { (second, first) };
+
+    let kinds = match (high == low, chars.get(2).copied()) {
+        (true, None) => vec![HandClassKind::Pair],
+        (false, None) => vec![HandClassKind::Suited, HandClassKind::Offsuit],
+        (false, Some('s' | 'S')) => vec![HandClassKind::Suited],
+        (false, Some('o' | 'O')) => vec![HandClassKind::Offsuit],
+        _ => return Err("Invalid hand class"),
+    };
+    Ok((high, low, kinds))
+}
+
+fn class_with(high: Rank, low: Rank, kind: HandClassKind) -> HandClass {
+    HandClass { high, low, kind }
+}
+
+// Classes covered by a single item without its weight
+fn parse_item(item: &str) -> Result<Vec<HandClass>, &'static str> {
+    if let Some((from, to)) = item.split_once('-') {
+        let (high_a, low_a, kinds_a) = parse_class_pattern(from.trim())?;
+        let (high_b, low_b, kinds_b) = parse_class_pattern(to.trim())?;
+        if kinds_a != kinds_b {
+            return Err("Both ends of a span must be the same kind of hand");
+        }
+
+        if kinds_a == [HandClassKind::Pair] {
+            let (top, bottom) = (high_a.max(high_b), high_a.min(high_b));
+            return Ok(Rank::ALL
+                .iter()
+                .filter(|&&rank| rank >= bottom && rank <= top)
+                .map(|&rank| class_with(rank, rank, HandClassKind::Pair))
+                .collect());
+        }
+        if high_a != high_b {
+            return Err("Both ends of a span must share the same high card");
+        }
+        let (top, bottom) = (low_a.max(low_b), low_a.min(low_b));
+        return Ok(Rank::ALL
+            .iter()
+            .filter(|&&rank| rank >= bottom && rank <= top)
+            .flat_map(|&low| kinds_a.iter().map(move |&kind| class_with(high_a, low, kind)))
+            .collect());
+    }
+
+    if let Some(base) = item.strip_suffix('+') {
+        let (high, low, kinds) = parse_class_pattern(base.trim())?;
+
+        // Pairs climb up to aces
+        if kinds == [HandClassKind::Pair] {
+            return Ok(Rank::ALL
+                .iter()
+                .filter(|&&rank| rank >= high)
+                .map(|&rank| class_with(rank, rank, HandClassKind::Pair))
+                .collect());
+        }
+
+        // Connectors keep their gap and climb up to the ace ("76s+" up to AKs),
+        // other hands raise the kicker up to one below the high card
+        let steps: Vec<(Rank, Rank)> = if low as u8 + 1 == high as u8 {
+            Rank::ALL
+                .iter()
+                .filter(|&&rank| rank >= high)
+                .map(|&rank| (rank, Rank::ALL[rank as usize - 3]))
+                .collect()
+        } else {
+            Rank::ALL
+                .iter()
+                .filter(|&&rank| rank >= low && rank < high)
+                .map(|&rank| (high, rank))
+                .collect()
+        };
+        return Ok(steps
+            .into_iter()
+            .flat_map(|(high, low)| kinds.iter().map(move |&kind| class_with(high, low, kind)))
+            .collect());
+    }
+
+    let (high, low, kinds) = parse_class_pattern(item)?;
+    Ok(kinds.into_iter().map(|kind| class_with(high, low, kind)).collect())
+}
+
+// Parses standard range notation: comma-separated pairs ("TT"), classes
+// ("AKs", "AKo", "AK" for both, "AKS" also read), spans ("TT+", "TT-77",
+// "ATs+", "A5s-A2s", "76s+" for suited connectors up to AKs), specific
+// combos ("AsKs") and an optional weight between 0 and 1 on any item
+// ("AKs:0.5"). Later items override the weight of combos already in the
+// range.
+pub fn parse_range(range_str: &str) -> Result<Range, &'static str> {
+    let mut range = Range::new();
+
+    for token in range_str.split(',') {
+        let token = token.trim();
+        if token.is_empty() {
+            continue;
+        }
+
+        let (item, weight) = match token.split_once(':') {
+            Some((item, weight)) => {
+                let weight: f64 = weight.trim().parse().map_err(|_| "Invalid weight")?;
+                if !(weight > 0.0 && weight <= 1.0) {
+                    return Err("Weights must be between 0 and 1");
+                }
+                (item.trim(), weight)
+            }
+            None => (token, 1.0),
+        };
+
+        let chars: Vec<char> = item.chars().collect();
+        if chars.len() == 4 && parse_suit_char(chars[1]).is_ok() && parse_suit_char(chars[3]).is_ok() {
+            let first = Card { rank: parse_rank_char(chars[0])?, suit: parse_suit_char(chars[1])? };
+            let second = Card { rank: parse_rank_char(chars[2])?, suit: parse_suit_char(chars[3])? };
+            if first == second {
+                return Err("Duplicate card");
+            }
+            range.add_combo([first, second], weight);
+            continue;
+        }
+
+        for class in parse_item(item)? {
+            range.add_class(class, weight);
+        }
+    }
+
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::{parse_cards, parse_hole_cards};
+
+    fn classes(range: &str) -> Vec<String> {
+        let range = parse_range(range).unwrap();
+        HandClass::all()
+            .into_iter()
+            .filter(|class| class.combos().iter().any(|&combo| range.weight(combo) > 0.0))
+            .map(|class| class.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_hand_class() {
+        let class = HandClass::of(parse_hole_cards("KS AS").unwrap());
+        assert_eq!(class.to_string(), "AKs");
+        assert_eq!(class.combos().len(), 4);
+        assert_eq!(HandClass::of(parse_hole_cards("10S 10H").unwrap()).to_string(), "TT");
+        assert_eq!(HandClass::of(parse_hole_cards("7D 2C").unwrap()).combos().len(), 12);
+        assert_eq!(HandClass::parse("kas").unwrap(), class);
+        assert_eq!(HandClass::parse("72o").unwrap().to_string(), "72o");
+        assert!(HandClass::parse("AK").is_err());
+        assert!(HandClass::parse("AKs+").is_err());
+
+        let all = HandClass::all();
+        assert_eq!(all.len(), 169);
+        assert_eq!(all[0].to_string(), "AA");
+        assert_eq!(all[1].to_string(), "AKs");
+        assert_eq!(all[13].to_string(), "AKo");
+        assert_eq!(all[168].to_string(), "22");
+        for (index, class) in all.iter().enumerate() {
+            assert_eq!(class.grid_index(), index);
+        }
+        assert_eq!(all.iter().map(|class| class.combo_count()).sum::<usize>(), 1326);
+    }
+
+    #[test]
+    fn test_parse_simple_items() {
+        assert_eq!(parse_range("AKs").unwrap().len(), 4);
+        assert_eq!(parse_range("AKo").unwrap().len(), 12);
+        assert_eq!(parse_range("AK").unwrap().len(), 16);
+        assert_eq!(parse_range("TT").unwrap().len(), 6);
+        assert_eq!(parse_range("AsKs").unwrap().len(), 1);
+        assert_eq!(parse_range("").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_parse_spans() {
+        assert_eq!(classes("TT+"), vec!["AA", "KK", "QQ", "JJ", "TT"]);
+        assert_eq!(classes("TT-88"), vec!["TT", "99", "88"]);
+        assert_eq!(classes("A5s-A2s"), vec!["A5s", "A4s", "A3s", "A2s"]);
+        assert_eq!(classes("KTs+"), vec!["KQs", "KJs", "KTs"]);
+        assert_eq!(classes("JTs+"), vec!["AKs", "KQs", "QJs", "JTs"]);
+        assert_eq!(classes("A9o+").len(), 5);
+        // AKs is also part of 76s+
+        assert_eq!(parse_range("TT+, AKs, A5s-A2s, KQo, 76s+").unwrap().len(), 30 + 16 + 12 + 32);
+    }
+
+    #[test]
+    fn test_parse_weights() {
+        let range = parse_range("AKs:0.5, AsKs").unwrap();
+        assert_eq!(range.weight(parse_hole_cards("AH KH").unwrap()), 0.5);
+        assert_eq!(range.weight(parse_hole_cards("KS AS").unwrap()), 1.0);
+        assert_eq!(range.weight(parse_hole_cards("AS KH").unwrap()), 0.0);
+        assert_eq!(range.total_weight(), 2.5);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_range("AKx").is_err());
+        assert!(parse_range("AAs").is_err());
+        assert!(parse_range("A5s-K2s").is_err());
+        assert!(parse_range("TT-A5s").is_err());
+        assert!(parse_range("AKs:1.5").is_err());
+        assert!(parse_range("AKs:0").is_err());
+        assert!(parse_range("AsAs").is_err());
+        assert!(parse_range("1K").is_err());
+    }
+
+    #[test]
+    fn test_card_removal() {
+        let range = parse_range("AA, AKs").unwrap();
+        let dead = parse_cards("AS").unwrap();
+        let live = range.without(&dead);
+
+        assert_eq!(live.len(), 3 + 3);
+        assert!(live.combos().iter().all(|(combo, _)| !combo.contains(&dead[0])));
+    }
+
+    #[test]
+    fn test_display_compresses() {
+        let canonical = |range: &str| parse_range(range).unwrap().to_string();
+
+        assert_eq!(canonical("TT+, AKs, A5s-A2s, KQo, 76s+"), "TT+, 76s+, A5s-A2s, KQo");
+        assert_eq!(canonical("AK, AQs"), "AK, AQs");
+        assert_eq!(canonical("88-66, 22"), "88-66, 22");
+        assert_eq!(canonical("ATs+, AJo+"), "AJ+, ATs");
+        assert_eq!(canonical("AKs:0.5, QQ"), "QQ, AKs:0.5");
+        assert_eq!(canonical("AsKs, AhKh"), "AhKh, AsKs");
+        assert_eq!(canonical("KK, AA"), "KK+");
+
+        // Connectors keep their own run when the row carries AK on
+        assert_eq!(canonical("ATs+, KQs, QJs"), "QJs+, ATs+");
+        assert_eq!(canonical("T9+"), "T9+");
+        assert_eq!(canonical("T9s+, 87o+"), "T9s+, 87o+");
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for text in ["TT+, AKs, A5s-A2s:0.5, KQo, 76s+", "AsKs, 22-55:0.25, K9o+", "AhAd, AA:0.5"] {
+            let range = parse_range(text).unwrap();
+            assert_eq!(parse_range(&range.to_string()).unwrap(), range);
+        }
+
+        // Canonical notation reads back as written
+        for text in ["TT+, T9s+, A5s-A2s, KQo", "QQ-88, 54+:0.5, ATs-A8s", "22+, 32s+, AQo+"] {
+            assert_eq!(parse_range(text).unwrap().to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_parse_upper_case_suffixes() {
+        assert_eq!(parse_range("AKS, T9O+, A5S-A2S").unwrap(), parse_range("AKs, T9o+, A5s-A2s").unwrap());
+        assert_eq!(HandClass::parse("KQO").unwrap(), HandClass::parse("KQo").unwrap());
+    }
+}
@@ -0,0 +1,421 @@
+use super::card::Card;
+use super::combinations::for_each_combination;
+use super::deck::Deck;
+use super::equity::showdown_values;
+use super::range::Range;
+use super::simulation::{Merge, Simulation};
+use super::value::HandValue;
+
+// Consecutive conflicting deals after which sampling gives up, as the ranges
+// then (almost) always share cards
+const MAX_REJECTIONS: u32 = 10_000;
+
+// Showdown results of a whole range, weighted by the combos' weights
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct RangeEquity {
+    pub win_rate: f64,
+    pub tie_rate: f64,
+    pub equity: f64,
+    // Standard error of `equity`, zero for exact results
+    pub std_error: f64,
+}
+
+// Equity of one combo of the hero's range against the other ranges
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ComboEquity {
+    pub combo: [Card; 2],
+    pub weight: f64,
+    // Boards this combo was seen on, zero if it never could be dealt
+    pub trials: u64,
+    pub equity: f64,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RangeEquityResult {
+    // Number of boards the results are based on, over every matchup
+    pub trials: u64,
+    pub ranges: Vec<RangeEquity>,
+    // Breakdown of the first range, the hero's
+    pub hero_combos: Vec<ComboEquity>,
+}
+
+// Weighted running totals; every showdown counts with the weight of the
+// matchup it comes from
+struct RangeTally {
+    trials: u64,
+    total_weight: f64,
+    wins: Vec<f64>,
+    ties: Vec<f64>,
+    shares: Vec<f64>,
+    squared_shares: Vec<f64>,
+    // Boards, weight and weighted share of each hero combo
+    hero: Vec<(u64, f64, f64)>,
+}
+
+impl RangeTally {
+    fn new(ranges: usize, hero_combos: usize) -> Self {
+        RangeTally {
+            trials: 0,
+            total_weight: 0.0,
+            wins: vec![0.0; ranges],
+            ties: vec![0.0; ranges],
+            shares: vec![0.0; ranges],
+            squared_shares: vec![0.0; ranges],
+            hero: vec![(0, 0.0, 0.0); hero_combos],
+        }
+    }
+
+    fn record(&mut self, values: &[HandValue], weight: f64, hero_combo: usize) {
+        let best = values.iter().max().copied();
+        let winners = values.iter().filter(|&&value| Some(value) == best).count();
+        let share = 1.0 / winners as f64;
+
+        self.trials += 1;
+        self.total_weight += weight;
+        for (player, value) in values.iter().enumerate() {
+            if Some(*value) != best {
+                continue;
+            }
+            if winners == 1 {
+                self.wins[player] += weight;
+            } else {
+                self.ties[player] += weight;
+            }
+            self.shares[player] += share * weight;
+            self.squared_shares[player] += share * share * weight;
+        }
+
+        let hero = &mut self.hero[hero_combo];
+        hero.0 += 1;
+        hero.1 += weight;
+        if Some(values[0]) == best {
+            hero.2 += share * weight;
+        }
+    }
+
+    fn result(&self, exact: bool, hero_combos: &[([Card; 2], f64)]) -> RangeEquityResult {
+        let total = self.total_weight;
+        let n = self.trials as f64;
+        let ranges = (0..self.wins.len())
+            .map(|player| {
+                let equity = self.shares[player] / total;
+                let variance = (self.squared_shares[player] / total - equity * equity).max(0.0);
+                RangeEquity {
+                    win_rate: self.wins[player] / total,
+                    tie_rate: self.ties[player] / total,
+                    equity,
+                    std_error: if exact { 0.0 } else { (variance / n).sqrt() },
+                }
+            })
+            .collect();
+
+        let hero_combos = hero_combos
+            .iter()
+            .zip(&self.hero)
+            .map(|(&(combo, weight), &(trials, seen, share))| ComboEquity {
+                combo,
+                weight,
+                trials,
+                equity: if trials == 0 { 0.0 } else { share / seen },
+            })
+            .collect();
+
+        RangeEquityResult { trials: self.trials, ranges, hero_combos }
+    }
+}
+
+// Weighted combos of a range, as `Range::combos` lists them
+type Combos = Vec<([Card; 2], f64)>;
+
+impl Merge for RangeTally {
+    fn merge(&mut self, other: Self) {
+        self.trials += other.trials;
+        self.total_weight += other.total_weight;
+        for player in 0..self.wins.len() {
+            self.wins[player] += other.wins[player];
+            self.ties[player] += other.ties[player];
+            self.shares[player] += other.shares[player];
+            self.squared_shares[player] += other.squared_shares[player];
+        }
+        for (hero, other) in self.hero.iter_mut().zip(other.hero) {
+            hero.0 += other.0;
+            hero.1 += other.1;
+            hero.2 += other.2;
+        }
+    }
+}
+
+fn mask(combo: [Card; 2]) -> u64 {
+    (1 << combo[0].index()) | (1 << combo[1].index())
+}
+
+// Combos of each range that don't use board or dead cards, and the cards
+// left once those are known
+fn prepare(
+    ranges: &[Range],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<(Vec<Combos>, Vec<Card>), &'static str> {
+    if ranges.len() < 2 {
+        return Err("At least two ranges are needed");
+    }
+    if board.len() > 5 {
+        return Err("A board has at most 5 cards");
+    }
+
+    let mut deck = Deck::new();
+    deck.remove(board).map_err(|_| "Duplicate card")?;
+    deck.remove(dead).map_err(|_| "Duplicate card")?;
+
+    let known: Vec<Card> = board.iter().chain(dead).copied().collect();
+    let live: Vec<Combos> = ranges.iter().map(|range| range.without(&known).combos()).collect();
+    if live.iter().any(|combos| combos.is_empty()) {
+        return Err("A range has no combo left once board and dead cards are removed");
+    }
+    if deck.remaining() < 2 * ranges.len() + 5 - board.len() {
+        return Err("Not enough cards left to complete the board");
+    }
+    Ok((live, deck.cards().to_vec()))
+}
+
+// Calls `visit` with every deal of one combo per range that shares no card,
+// as (combo position in its range, combo) pairs, and the product of weights
+fn for_each_matchup(
+    ranges: &[Combos],
+    used: u64,
+    weight: f64,
+    chosen: &mut Vec<(usize, [Card; 2])>,
+    visit: &mut impl FnMut(&[(usize, [Card; 2])], f64),
+) {
+    let Some(combos) = ranges.get(chosen.len()) else {
+        visit(chosen, weight);
+        return;
+    };
+    for (i, &(combo, combo_weight)) in combos.iter().enumerate() {
+        if used & mask(combo) != 0 {
+            continue;
+        }
+        chosen.push((i, combo));
+        for_each_matchup(ranges, used | mask(combo), weight * combo_weight, chosen, visit);
+        chosen.pop();
+    }
+}
+
+// Exact equity between weighted ranges, over every matchup of combos that
+// don't share cards and every runout of the board
+pub fn exact_range_equity(
+    ranges: &[Range],
+    board: &[Card],
+    dead: &[Card],
+) -> Result<RangeEquityResult, &'static str> {
+    let (live, deck) = prepare(ranges, board, dead)?;
+    let missing = 5 - board.len();
+
+    let mut tally = RangeTally::new(ranges.len(), live[0].len());
+    let mut holdings = Vec::with_capacity(ranges.len());
+    let mut full_board = board.to_vec();
+    let mut values = Vec::with_capacity(ranges.len());
+
+    for_each_matchup(&live, 0, 1.0, &mut Vec::new(), &mut |chosen, weight| {
+        holdings.clear();
+        holdings.extend(chosen.iter().map(|&(_, combo)| combo));
+        let used = holdings.iter().fold(0, |used, &combo| used | mask(combo));
+        let remaining: Vec<Card> = deck.iter().filter(|card| used & (1 << card.index()) == 0).copied().collect();
+
+        for_each_combination(&remaining, missing, |runout| {
+            full_board.truncate(board.len());
+            full_board.extend_from_slice(runout);
+
+            showdown_values(&holdings, &full_board, &mut values);
+            tally.record(&values, weight, chosen[0].0);
+        });
+    });
+
+    if tally.trials == 0 {
+        return Err("No possible matchup between the ranges");
+    }
+    Ok(tally.result(true, &live[0]))
+}
+
+// Estimates equity between weighted ranges: each iteration deals every range a
+// combo by weight, dealing again on card conflicts, then a random runout.
+// Iterations are spread over every core; the result only depends on the seed.
+pub fn monte_carlo_range_equity(
+    ranges: &[Range],
+    board: &[Card],
+    dead: &[Card],
+    iterations: u64,
+    seed: u64,
+) -> Result<RangeEquityResult, &'static str> {
+    if iterations == 0 {
+        return Err("At least one iteration is needed");
+    }
+
+    let (live, deck) = prepare(ranges, board, dead)?;
+    let missing = 5 - board.len();
+
+    // Running weight totals, to pick a combo with a single uniform draw
+    let cumulative: Vec<Vec<f64>> = live
+        .iter()
+        .map(|combos| {
+            combos
+                .iter()
+                .scan(0.0, |total, &(_, weight)| {
+                    *total += weight;
+                    Some(*total)
+                })
+                .collect()
+        })
+        .collect();
+
+    let total = Ok(RangeTally::new(ranges.len(), live[0].len()));
+    let tally = Simulation::new(seed).run(iterations, total, |trials, rng| {
+        let mut tally = RangeTally::new(ranges.len(), live[0].len());
+        let mut chosen = vec![0; ranges.len()];
+        let mut holdings = vec![live[0][0].0; ranges.len()];
+        let mut full_board = board.to_vec();
+        let mut values = Vec::with_capacity(ranges.len());
+
+        for _ in 0..trials {
+            let mut rejections = 0;
+            let used = loop {
+                let mut used = 0;
+                let mut conflict = false;
+                for (player, totals) in cumulative.iter().enumerate() {
+                    let draw = rng.next_f64() * totals[totals.len() - 1];
+                    let i = totals.partition_point(|&total| total <= draw).min(totals.len() - 1);
+                    let combo = live[player][i].0;
+                    if used & mask(combo) != 0 {
+                        conflict = true;
+                        break;
+                    }
+                    used |= mask(combo);
+                    chosen[player] = i;
+                    holdings[player] = combo;
+                }
+                if !conflict {
+                    break used;
+                }
+                rejections += 1;
+                if rejections == MAX_REJECTIONS {
+                    return Err("No possible matchup between the ranges");
+                }
+            };
+
+            // Partial shuffle: only the cards that complete the board are drawn
+            let mut remaining: Vec<Card> =
+                deck.iter().filter(|card| used & (1 << card.index()) == 0).copied().collect();
+            for i in 0..missing {
+                let j = i + rng.below(remaining.len() - i);
+                remaining.swap(i, j);
+            }
+            full_board.truncate(board.len());
+            full_board.extend_from_slice(&remaining[..missing]);
+
+            showdown_values(&holdings, &full_board, &mut values);
+            tally.record(&values, 1.0, chosen[0]);
+        }
+        Ok(tally)
+    })?;
+
+    Ok(tally.result(false, &live[0]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::equity::exact_equity;
+    use super::super::parser::{parse_cards, parse_hole_cards};
+    use super::super::range::parse_range;
+
+    fn ranges(ranges: &[&str]) -> Vec<Range> {
+        ranges.iter().map(|range| parse_range(range).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_single_combos_match_hand_equity() {
+        let board = parse_cards("2C 7D 9H").unwrap();
+        let result = exact_range_equity(&ranges(&["AsAh", "KsKh"]), &board, &[]).unwrap();
+        let hands = [parse_hole_cards("AS AH").unwrap(), parse_hole_cards("KS KH").unwrap()];
+        let expected = exact_equity(&hands, &board, &[]).unwrap();
+
+        assert_eq!(result.trials, expected.trials);
+        assert!((result.ranges[0].equity - expected.players[0].equity).abs() < 1e-12);
+        assert!((result.ranges[1].win_rate - expected.players[1].win_rate()).abs() < 1e-12);
+        assert_eq!(result.ranges[0].std_error, 0.0);
+    }
+
+    #[test]
+    fn test_card_conflicts() {
+        // Only AdAc is left to the villain once the hero holds two aces
+        let board = parse_cards("2C 7D 9H 3S").unwrap();
+        let result = exact_range_equity(&ranges(&["AsAh", "AA"]), &board, &[]).unwrap();
+        assert_eq!(result.trials, 44);
+        assert_eq!(result.ranges[0].tie_rate, 1.0);
+
+        // Board and dead cards remove combos too
+        let dead = parse_cards("AD").unwrap();
+        let result = exact_range_equity(&ranges(&["KK", "AA"]), &board, &dead).unwrap();
+        assert_eq!(result.hero_combos.len(), 6);
+        assert_eq!(result.trials, 6 * 3 * 43);
+    }
+
+    #[test]
+    fn test_weighted_ranges() {
+        let board = parse_cards("2C 7D 9H 3S").unwrap();
+        let hero = parse_hole_cards("AS AH").unwrap();
+        let against = |villain: &str| {
+            exact_equity(&[hero, parse_hole_cards(villain).unwrap()], &board, &[]).unwrap().players[0].equity
+        };
+
+        let result = exact_range_equity(&ranges(&["AsAh", "KsKh, QsQh:0.5"]), &board, &[]).unwrap();
+        let expected = (against("KS KH") + 0.5 * against("QS QH")) / 1.5;
+        assert!((result.ranges[0].equity - expected).abs() < 1e-12);
+        assert!((result.ranges[0].equity + result.ranges[1].equity - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_hero_combo_breakdown() {
+        let board = parse_cards("KC 7D 2H").unwrap();
+        let result = exact_range_equity(&ranges(&["AA, KK", "QQ"]), &board, &[]).unwrap();
+
+        assert_eq!(result.hero_combos.len(), 9);
+        let villains = parse_range("QQ").unwrap().combos();
+        for combo in &result.hero_combos {
+            // Every villain combo is equally likely here
+            let expected = villains
+                .iter()
+                .map(|&(villain, _)| exact_equity(&[combo.combo, villain], &board, &[]).unwrap().players[0].equity)
+                .sum::<f64>()
+                / villains.len() as f64;
+            assert!((combo.equity - expected).abs() < 1e-12);
+            assert_eq!(combo.weight, 1.0);
+            assert_eq!(combo.trials, 6 * 990);
+        }
+    }
+
+    #[test]
+    fn test_monte_carlo_agrees_with_exact() {
+        let board = parse_cards("JH 10H 4C").unwrap();
+        let players = ranges(&["AA, KQs, 98s:0.5", "JJ+, AK, AQs"]);
+        let exact = exact_range_equity(&players, &board, &[]).unwrap();
+        let estimate = monte_carlo_range_equity(&players, &board, &[], 20_000, 3).unwrap();
+
+        assert_eq!(estimate.trials, 20_000);
+        let error = estimate.ranges[0].std_error;
+        assert!(error > 0.0 && error < 0.01);
+        assert!((estimate.ranges[0].equity - exact.ranges[0].equity).abs() < 4.0 * error);
+        assert_eq!(estimate, monte_carlo_range_equity(&players, &board, &[], 20_000, 3).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let board = parse_cards("AS 2C 3D").unwrap();
+        assert!(exact_range_equity(&ranges(&["AA"]), &[], &[]).is_err());
+        assert!(exact_range_equity(&ranges(&["AsAh", "AsAh"]), &[], &[]).is_err());
+        assert!(monte_carlo_range_equity(&ranges(&["AsAh", "AsAh"]), &[], &[], 10, 1).is_err());
+        assert!(monte_carlo_range_equity(&ranges(&["AA", "KK"]), &[], &[], 0, 1).is_err());
+        assert!(exact_range_equity(&ranges(&["AsKs", "QQ"]), &board, &[]).is_err());
+        assert!(exact_range_equity(&ranges(&["KK", "QQ"]), &board, &parse_cards("2C").unwrap()).is_err());
+    }
+}
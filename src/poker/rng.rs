@@ -0,0 +1,103 @@
+// Small seedable pseudo-random generator (SplitMix64), good enough for
+// shuffling and simulations and fully reproducible from its seed
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    // One of many independent generators derived from a single seed, e.g. one
+    // per chunk of a parallel simulation
+    pub fn stream(seed: u64, stream: u64) -> Self {
+        let mut mixer = Rng::new(seed ^ stream.wrapping_mul(0xD605_BBB5_8C8A_BBB5));
+        Rng::new(mixer.next_u64())
+    }
+
+    // Seeded from the system clock, for when reproducibility doesn't matter
+    pub fn from_time() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or(0);
+        Rng::new(nanos)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Uniform integer in `0..bound`
+    pub fn below(&mut self, bound: usize) -> usize {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+
+    // Uniform float in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Fisher-Yates shuffle
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut first = Rng::new(42);
+        let mut second = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(first.next_u64(), second.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_streams_differ() {
+        let first: Vec<u64> = (0..4).map(|stream| Rng::stream(42, stream).next_u64()).collect();
+        let again: Vec<u64> = (0..4).map(|stream| Rng::stream(42, stream).next_u64()).collect();
+        assert_eq!(first, again);
+        for i in 0..4 {
+            for j in i + 1..4 {
+                assert_ne!(first[i], first[j]);
+            }
+        }
+        assert_ne!(Rng::stream(42, 0).next_u64(), Rng::stream(43, 0).next_u64());
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            seen[rng.below(6)] = true;
+            let x = rng.next_f64();
+            assert!((0.0..1.0).contains(&x));
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn test_shuffle_is_a_permutation() {
+        let mut items: Vec<u32> = (0..52).collect();
+        Rng::new(3).shuffle(&mut items);
+        assert_ne!(items, (0..52).collect::<Vec<u32>>());
+        items.sort();
+        assert_eq!(items, (0..52).collect::<Vec<u32>>());
+    }
+}
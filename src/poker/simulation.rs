@@ -0,0 +1,171 @@
+use super::rng::Rng;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+// Trials per chunk in `Simulation::run`; chunks, not threads, are the unit
+// that gets its own generator
+const CHUNK_TRIALS: u64 = 1024;
+
+// Partial results of a simulation that can be combined, e.g. two tallies of
+// showdowns over different boards
+pub trait Merge {
+    fn merge(&mut self, other: Self);
+}
+
+// A failed chunk fails the whole simulation
+impl<R: Merge, E> Merge for Result<R, E> {
+    fn merge(&mut self, other: Self) {
+        match other {
+            Ok(other) => {
+                if let Ok(total) = self {
+                    total.merge(other);
+                }
+            }
+            Err(e) => {
+                if self.is_ok() {
+                    *self = Err(e);
+                }
+            }
+        }
+    }
+}
+
+// Runs CPU-bound jobs on several threads. Work is split into jobs that each
+// get their own generator, derived from the seed and the job's position, and
+// results are combined in job order, so the outcome only depends on the seed
+// and never on the number of threads or on scheduling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Simulation {
+    seed: u64,
+    threads: usize,
+}
+
+impl Simulation {
+    // Uses every available core
+    pub fn new(seed: u64) -> Self {
+        let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+        Simulation { seed, threads }
+    }
+
+    pub fn with_threads(self, threads: usize) -> Self {
+        Simulation { threads: threads.max(1), ..self }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    // Calls `job(index, rng)` for every index in `0..jobs`, returning the
+    // results in index order. Threads pick the next job as they become free.
+    pub fn map<R, F>(&self, jobs: usize, job: F) -> Vec<R>
+    where
+        R: Send,
+        F: Fn(usize, &mut Rng) -> R + Sync,
+    {
+        let next = AtomicUsize::new(0);
+        let threads = self.threads.min(jobs).max(1);
+
+        let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            if index >= jobs {
+                                return done;
+                            }
+                            let mut rng = Rng::stream(self.seed, index as u64);
+                            done.push((index, job(index, &mut rng)));
+                        }
+                    })
+                })
+                .collect();
+            workers.into_iter().flat_map(|worker| worker.join().unwrap()).collect()
+        });
+
+        results.sort_by_key(|&(index, _)| index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    // Splits `trials` into fixed-size chunks, calls `simulate(chunk_trials,
+    // rng)` for each one and merges the partial results into `total`
+    pub fn run<R, F>(&self, trials: u64, mut total: R, simulate: F) -> R
+    where
+        R: Merge + Send,
+        F: Fn(u64, &mut Rng) -> R + Sync,
+    {
+        let chunks = trials.div_ceil(CHUNK_TRIALS) as usize;
+        let partials = self.map(chunks, |chunk, rng| {
+            let start = chunk as u64 * CHUNK_TRIALS;
+            simulate(CHUNK_TRIALS.min(trials - start), rng)
+        });
+        for partial in partials {
+            total.merge(partial);
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sum and count of uniform draws
+    #[derive(Debug, PartialEq)]
+    struct Mean {
+        sum: f64,
+        count: u64,
+    }
+
+    impl Merge for Mean {
+        fn merge(&mut self, other: Self) {
+            self.sum += other.sum;
+            self.count += other.count;
+        }
+    }
+
+    fn mean(simulation: Simulation, trials: u64) -> Mean {
+        simulation.run(trials, Mean { sum: 0.0, count: 0 }, |trials, rng| Mean {
+            sum: (0..trials).map(|_| rng.next_f64()).sum(),
+            count: trials,
+        })
+    }
+
+    #[test]
+    fn test_results_do_not_depend_on_threads() {
+        let single = mean(Simulation::new(11).with_threads(1), 100_000);
+        for threads in [2, 3, 8] {
+            assert_eq!(mean(Simulation::new(11).with_threads(threads), 100_000), single);
+        }
+        assert_ne!(mean(Simulation::new(12), 100_000), single);
+
+        assert_eq!(single.count, 100_000);
+        assert!((single.sum / single.count as f64 - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_map_keeps_job_order() {
+        let simulation = Simulation::new(5).with_threads(4);
+        let squares = simulation.map(50, |index, _| index * index);
+        assert_eq!(squares, (0..50).map(|index| index * index).collect::<Vec<_>>());
+
+        // Each job gets its own stream
+        let draws = simulation.map(50, |_, rng| rng.next_u64());
+        assert_eq!(draws, Simulation::new(5).with_threads(1).map(50, |_, rng| rng.next_u64()));
+        assert_ne!(draws[0], draws[1]);
+    }
+
+    #[test]
+    fn test_uneven_and_empty_work() {
+        let simulation = Simulation::new(1).with_threads(0);
+        assert_eq!(simulation.threads(), 1);
+        assert_eq!(mean(simulation, 0), Mean { sum: 0.0, count: 0 });
+        assert_eq!(mean(simulation, 2_500).count, 2_500);
+        assert!(simulation.map(0, |index, _| index).is_empty());
+    }
+}
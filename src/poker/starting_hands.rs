@@ -0,0 +1,178 @@
+use super::card::{Card, Rank};
+use super::random_opponents::precomputed_vs_random;
+use super::range::{parse_range, HandClass, HandClassKind};
+use std::sync::OnceLock;
+
+// Sklansky-Malmuth groups 1 to 8; every other hand is unranked
+const SKLANSKY_GROUPS: [&str; 8] = [
+    "AA, KK, QQ, JJ, AKs",
+    "TT, AQs, AJs, KQs, AKo",
+    "99, JTs, QJs, KJs, ATs, AQo",
+    "T9s, KQo, 88, QTs, 98s, J9s, AJo, KTs",
+    "77, 87s, Q9s, T8s, KJo, QJo, JTo, 76s, 97s, A9s-A2s, 65s",
+    "66, ATo, 55, 86s, KTo, QTo, 54s, K9s, J8s, 75s",
+    "44, J9o, 64s, T9o, 53s, 33, 98o, 43s, 22, K8s-K2s, T7s, Q8s",
+    "87o, A9o, Q9o, 76o, 42s, 32s, 96s, 85s, J8o, J7s, 65o, 54o, 74s, K9o, T8o",
+];
+
+// Scores and ranks of a starting hand class
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct StartingHandRanking {
+    pub class: HandClass,
+    pub chen_score: i32,
+    pub sklansky_group: Option<u8>,
+    // Pre-flop all-in equity against random hands, ties counting half
+    pub equity: f64,
+    // 1 for the best class by equity
+    pub equity_rank: usize,
+}
+
+// Bill Chen's formula: points for the highest card (ace 10, king 8, queen 7,
+// jack 6, others half their rank), doubled for a pair (at least 5), plus 2 if
+// suited, minus 1, 2, 4 or 5 for gaps of one, two, three or more, plus 1 for
+// connected or one-gap cards below a queen; halves are rounded up
+pub fn chen_score(hole_cards: [Card; 2]) -> i32 {
+    class_chen_score(HandClass::of(hole_cards))
+}
+
+fn class_chen_score(class: HandClass) -> i32 {
+    // In half points, to stay exact
+    let high = match class.high {
+        Rank::Ace => 20,
+        Rank::King => 16,
+        Rank::Queen => 14,
+        Rank::Jack => 12,
+        rank => rank as i32,
+    };
+    let halves = match class.kind {
+        HandClassKind::Pair => (2 * high).max(10),
+        kind => {
+            let gap = class.high as i32 - class.low as i32 - 1;
+            let suited = if kind == HandClassKind::Suited { 4 } else { 0 };
+            let penalty = match gap {
+                0 => 0,
+                1 => 2,
+                2 => 4,
+                3 => 8,
+                _ => 10,
+            };
+            let bonus = if gap <= 1 && class.high < Rank::Queen { 2 } else { 0 };
+            high + suited - penalty + bonus
+        }
+    };
+    (halves + 1).div_euclid(2)
+}
+
+fn sklansky_table() -> &'static [Option<u8>; 169] {
+    static TABLE: OnceLock<[Option<u8>; 169]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [None; 169];
+        for (group, classes) in SKLANSKY_GROUPS.iter().enumerate() {
+            let range = parse_range(classes).expect("Sklansky groups are valid ranges");
+            for class in HandClass::all() {
+                if range.weight(class.combos()[0]) > 0.0 {
+                    table[class.grid_index()] = Some(group as u8 + 1);
+                }
+            }
+        }
+        table
+    })
+}
+
+// 1 (AA, KK, QQ, JJ, AKs) to 8, None for unranked hands
+pub fn sklansky_group(hole_cards: [Card; 2]) -> Option<u8> {
+    sklansky_table()[HandClass::of(hole_cards).grid_index()]
+}
+
+// Every class from the best to the worst by pre-flop equity against
+// `opponents` random hands, from the precomputed table
+pub fn starting_hand_rankings(opponents: usize) -> Result<Vec<StartingHandRanking>, &'static str> {
+    let mut rankings = HandClass::all()
+        .into_iter()
+        .map(|class| {
+            let odds = precomputed_vs_random(class, opponents)?;
+            Ok(StartingHandRanking {
+                class,
+                chen_score: class_chen_score(class),
+                sklansky_group: sklansky_table()[class.grid_index()],
+                equity: odds.win_rate + odds.tie_rate / 2.0,
+                equity_rank: 0,
+            })
+        })
+        .collect::<Result<Vec<_>, &'static str>>()?;
+
+    // Equal equities keep grid order, so the ranking is stable
+    rankings.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+    for (i, ranking) in rankings.iter_mut().enumerate() {
+        ranking.equity_rank = i + 1;
+    }
+    Ok(rankings)
+}
+
+pub fn starting_hand_ranking(hole_cards: [Card; 2], opponents: usize) -> Result<StartingHandRanking, &'static str> {
+    let class = HandClass::of(hole_cards);
+    let rankings = starting_hand_rankings(opponents)?;
+    Ok(*rankings.iter().find(|ranking| ranking.class == class).expect("Every class is ranked"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::parse_hole_cards;
+
+    fn hole(cards: &str) -> [Card; 2] {
+        parse_hole_cards(cards).unwrap()
+    }
+
+    #[test]
+    fn test_chen_score() {
+        assert_eq!(chen_score(hole("AS AH")), 20);
+        assert_eq!(chen_score(hole("KS KH")), 16);
+        assert_eq!(chen_score(hole("2S 2H")), 5);
+        assert_eq!(chen_score(hole("AS KS")), 12);
+        assert_eq!(chen_score(hole("AS KH")), 10);
+        assert_eq!(chen_score(hole("JS 10S")), 9);
+        // 3.5 points for the seven, 5 off for the gap, rounded up
+        assert_eq!(chen_score(hole("7S 2H")), -1);
+        // 4.5 for the nine, 2 suited, 1 off for the gap, 1 for being low
+        assert_eq!(chen_score(hole("9H 7H")), 7);
+        assert_eq!(chen_score(hole("5C 5D")), 5);
+        assert_eq!(chen_score(hole("QD JD")), 9);
+    }
+
+    #[test]
+    fn test_sklansky_groups() {
+        assert_eq!(sklansky_group(hole("AS AH")), Some(1));
+        assert_eq!(sklansky_group(hole("AS KS")), Some(1));
+        assert_eq!(sklansky_group(hole("AS KH")), Some(2));
+        assert_eq!(sklansky_group(hole("AS 5S")), Some(5));
+        assert_eq!(sklansky_group(hole("KS 2S")), Some(7));
+        assert_eq!(sklansky_group(hole("KS 9H")), Some(8));
+        assert_eq!(sklansky_group(hole("7S 2H")), None);
+
+        // Group sizes, in classes
+        let mut sizes = [0; 8];
+        for group in sklansky_table().iter().flatten() {
+            sizes[*group as usize - 1] += 1;
+        }
+        assert_eq!(sizes, [5, 5, 6, 8, 18, 10, 18, 15]);
+    }
+
+    #[test]
+    fn test_equity_ranking() {
+        let rankings = starting_hand_rankings(1).unwrap();
+        assert_eq!(rankings.len(), 169);
+        assert_eq!(rankings[0].class.to_string(), "AA");
+        assert_eq!(rankings[1].class.to_string(), "KK");
+        assert_eq!(rankings[168].class.to_string(), "32o");
+        assert!(rankings.windows(2).all(|pair| pair[0].equity >= pair[1].equity));
+        assert!(rankings.iter().enumerate().all(|(i, ranking)| ranking.equity_rank == i + 1));
+
+        // Suited connectors gain against many opponents
+        let heads_up = starting_hand_ranking(hole("8S 7S"), 1).unwrap();
+        let nine_way = starting_hand_ranking(hole("8S 7S"), 9).unwrap();
+        assert!(nine_way.equity_rank < heads_up.equity_rank);
+        assert_eq!(heads_up.sklansky_group, Some(5));
+        assert!(starting_hand_rankings(0).is_err());
+    }
+}
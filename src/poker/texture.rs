@@ -0,0 +1,374 @@
+use super::card::{Card, HandType, Rank, Suit};
+use super::combinations::for_each_combination;
+use super::deck::Deck;
+use super::value::HandValue;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Pairing {
+    Unpaired,
+    Paired,
+    TwoPair,
+    Trips,
+    FullHouse,
+    Quads,
+}
+
+// How the board's cards share suits
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SuitTexture {
+    // No two cards of the same suit
+    Rainbow,
+    // At most two cards of any suit: flush draws but no flush yet
+    TwoTone,
+    // Three or more cards of one suit, but not all of them: a flush is
+    // possible
+    FlushPossible,
+    // Every card of the same suit
+    Monotone,
+}
+
+// How many different two-card holdings make a straight with the board
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+pub enum Connectedness {
+    Disconnected,
+    // Up to 32 holdings, as many as two pairs of ranks off the board give
+    SemiConnected,
+    // More
+    Connected,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+pub enum Wetness {
+    Dry,
+    SemiWet,
+    Wet,
+}
+
+// Hole cards of one suit that give four to a flush
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FlushDraw {
+    pub suit: Suit,
+    pub hole_cards_needed: usize,
+}
+
+// Hole card ranks that give a straight draw, and the ranks completing it
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StraightDraw {
+    pub hole_ranks: Vec<Rank>,
+    pub outs: Vec<Rank>,
+}
+
+impl StraightDraw {
+    // Two completing ranks, whether open-ended or a double gutshot
+    pub fn is_open_ended(&self) -> bool {
+        self.outs.len() >= 2
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoardTexture {
+    pub pairing: Pairing,
+    pub suits: SuitTexture,
+    pub connectedness: Connectedness,
+    // Number of two-card holdings from the unseen cards whose best hand is a
+    // straight
+    pub straight_combos: usize,
+    pub straight_possible: bool,
+    pub flush_possible: bool,
+    pub wetness: Wetness,
+    // Draws to come, empty on the river
+    pub flush_draws: Vec<FlushDraw>,
+    pub straight_draws: Vec<StraightDraw>,
+}
+
+impl BoardTexture {
+    // Classifies a flop, turn or river. Wetness adds up one point for a
+    // two-tone board, two for a flush-possible or monotone one, one for a
+    // semi-connected board and two for a connected one, less one on a paired
+    // board: 0-1 is dry, 2 semi-wet and 3 or more wet.
+    pub fn classify(board: &[Card]) -> Result<Self, &'static str> {
+        if board.len() < 3 || board.len() > 5 {
+            return Err("A board has 3 to 5 cards");
+        }
+        Deck::new().remove(board).map_err(|_| "Duplicate card")?;
+
+        let mut rank_counts = [0usize; 13];
+        let mut suit_counts = [0usize; 4];
+        for card in board {
+            rank_counts[card.rank as usize - 2] += 1;
+            suit_counts[card.suit as usize] += 1;
+        }
+
+        let pairs = rank_counts.iter().filter(|&&count| count == 2).count();
+        let pairing = match rank_counts.iter().max() {
+            Some(4) => Pairing::Quads,
+            Some(3) if pairs > 0 => Pairing::FullHouse,
+            Some(3) => Pairing::Trips,
+            Some(2) if pairs > 1 => Pairing::TwoPair,
+            Some(2) => Pairing::Paired,
+            _ => Pairing::Unpaired,
+        };
+
+        let most_suited = suit_counts.iter().copied().max().unwrap_or(0);
+        let suits = match most_suited {
+            1 => SuitTexture::Rainbow,
+            2 => SuitTexture::TwoTone,
+            count if count == board.len() => SuitTexture::Monotone,
+            _ => SuitTexture::FlushPossible,
+        };
+
+        let board_mask = board.iter().fold(0u16, |mask, card| mask | rank_bit(card.rank));
+        let straight_combos = straight_combos(board);
+        let connectedness = match straight_combos {
+            0 => Connectedness::Disconnected,
+            1..=32 => Connectedness::SemiConnected,
+            _ => Connectedness::Connected,
+        };
+
+        let score: u32 = match suits {
+            SuitTexture::Rainbow => 0,
+            SuitTexture::TwoTone => 1,
+            SuitTexture::FlushPossible | SuitTexture::Monotone => 2,
+        } + match connectedness {
+            Connectedness::Disconnected => 0,
+            Connectedness::SemiConnected => 1,
+            Connectedness::Connected => 2,
+        };
+        let score = if pairing == Pairing::Unpaired { score } else { score.saturating_sub(1) };
+        let wetness = match score {
+            0 | 1 => Wetness::Dry,
+            2 => Wetness::SemiWet,
+            _ => Wetness::Wet,
+        };
+
+        let (flush_draws, straight_draws) = if board.len() == 5 {
+            (Vec::new(), Vec::new())
+        } else {
+            let flush_draws = Suit::ALL
+                .iter()
+                .zip(suit_counts)
+                .filter(|&(_, count)| (2..4).contains(&count))
+                .map(|(&suit, count)| FlushDraw { suit, hole_cards_needed: 4 - count })
+                .collect();
+            (flush_draws, straight_draws(board_mask))
+        };
+
+        Ok(BoardTexture {
+            pairing,
+            suits,
+            connectedness,
+            straight_combos,
+            straight_possible: has_straight(board_mask) || straight_combos > 0,
+            flush_possible: most_suited >= 3,
+            wetness,
+            flush_draws,
+            straight_draws,
+        })
+    }
+}
+
+// Holdings whose best hand with the board is a straight, a straight on the
+// board included; pairs and cards matching the board count like any other
+fn straight_combos(board: &[Card]) -> usize {
+    let mut deck = Deck::new();
+    deck.remove(board).unwrap();
+    let mut cards = board.to_vec();
+    let mut count = 0;
+    for_each_combination(deck.cards(), 2, |holding| {
+        cards.truncate(board.len());
+        cards.extend_from_slice(holding);
+        if HandValue::from_cards(&cards).hand_type() == HandType::Straight {
+            count += 1;
+        }
+    });
+    count
+}
+
+fn rank_bit(rank: Rank) -> u16 {
+    1 << (rank as usize - 2)
+}
+
+fn has_straight(mask: u16) -> bool {
+    // The ace also plays low, below the two
+    let mask = (mask << 1) | (mask >> 12);
+    (0..10).any(|low| (mask >> low) & 0b11111 == 0b11111)
+}
+
+// Pairs of distinct ranks, higher first, that aren't on the board; paired
+// hole cards can't help a straight more than one card of the rank does
+fn hole_rank_pairs(board_mask: u16) -> impl Iterator<Item = (Rank, Rank)> {
+    let missing: Vec<Rank> =
+        Rank::ALL.iter().rev().copied().filter(|&rank| board_mask & rank_bit(rank) == 0).collect();
+    (0..missing.len()).flat_map(move |i| {
+        let missing = missing.clone();
+        (i + 1..missing.len()).map(move |j| (missing[i], missing[j]))
+    })
+}
+
+// Ranks that complete a straight for a board plus hole ranks, leaving out
+// those that put a straight on the board by itself
+fn straight_outs(board_mask: u16, mask: u16) -> Vec<Rank> {
+    Rank::ALL
+        .iter()
+        .rev()
+        .copied()
+        .filter(|&rank| has_straight(mask | rank_bit(rank)) && !has_straight(board_mask | rank_bit(rank)))
+        .collect()
+}
+
+// Straight draws with one hole card, then those needing both, where each
+// hole card adds outs the other one doesn't give on its own
+fn straight_draws(board_mask: u16) -> Vec<StraightDraw> {
+    let single_outs = |rank: Rank| -> Vec<Rank> {
+        let mask = board_mask | rank_bit(rank);
+        if board_mask & rank_bit(rank) != 0 || has_straight(mask) {
+            Vec::new()
+        } else {
+            straight_outs(board_mask, mask)
+        }
+    };
+
+    let mut draws: Vec<StraightDraw> = Rank::ALL
+        .iter()
+        .rev()
+        .map(|&rank| StraightDraw { hole_ranks: vec![rank], outs: single_outs(rank) })
+        .filter(|draw| !draw.outs.is_empty())
+        .collect();
+
+    for (a, b) in hole_rank_pairs(board_mask) {
+        let mask = board_mask | rank_bit(a) | rank_bit(b);
+        if has_straight(mask) {
+            continue;
+        }
+        let outs = straight_outs(board_mask, mask);
+        let (outs_a, outs_b) = (single_outs(a), single_outs(b));
+        if outs.iter().any(|rank| !outs_a.contains(rank) && !outs_b.contains(rank)) {
+            draws.push(StraightDraw { hole_ranks: vec![a, b], outs });
+        }
+    }
+    draws
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::parse_cards;
+
+    fn texture(board: &str) -> BoardTexture {
+        BoardTexture::classify(&parse_cards(board).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_dry_board() {
+        let board = texture("AH KD 2C");
+        assert_eq!(board.pairing, Pairing::Unpaired);
+        assert_eq!(board.suits, SuitTexture::Rainbow);
+        assert_eq!(board.connectedness, Connectedness::Disconnected);
+        assert_eq!(board.wetness, Wetness::Dry);
+        assert!(!board.straight_possible && !board.flush_possible);
+        assert!(board.flush_draws.is_empty());
+
+        // Gutshots only: QJ, QT, JT towards the broadway, 54, 53, 43 towards the wheel
+        let draws: Vec<Vec<Rank>> = board.straight_draws.iter().map(|draw| draw.hole_ranks.clone()).collect();
+        assert_eq!(draws.len(), 6);
+        assert_eq!(draws[0], vec![Rank::Queen, Rank::Jack]);
+        assert_eq!(board.straight_draws[0].outs, vec![Rank::Ten]);
+        assert!(board.straight_draws.iter().all(|draw| !draw.is_open_ended()));
+    }
+
+    #[test]
+    fn test_wet_board() {
+        let board = texture("9H 8H 7C");
+        assert_eq!(board.suits, SuitTexture::TwoTone);
+        assert_eq!(board.connectedness, Connectedness::Connected);
+        // JT, T6 and 65 in every suit
+        assert_eq!(board.straight_combos, 48);
+        assert_eq!(board.wetness, Wetness::Wet);
+        assert_eq!(board.flush_draws, vec![FlushDraw { suit: Suit::Hearts, hole_cards_needed: 2 }]);
+
+        let singles: Vec<&StraightDraw> =
+            board.straight_draws.iter().filter(|draw| draw.hole_ranks.len() == 1).collect();
+        assert_eq!(singles.len(), 4);
+        assert_eq!(singles[0].hole_ranks, vec![Rank::Jack]);
+        assert_eq!(singles[1].outs, vec![Rank::Jack, Rank::Six]);
+        assert!(singles[1].is_open_ended());
+    }
+
+    #[test]
+    fn test_pairing() {
+        assert_eq!(texture("5S 5D 5H").pairing, Pairing::Trips);
+        assert_eq!(texture("5S 5D KH KD").pairing, Pairing::TwoPair);
+        assert_eq!(texture("5S 5D 5H KD KC").pairing, Pairing::FullHouse);
+        assert_eq!(texture("5S 5D 5H 5C").pairing, Pairing::Quads);
+        assert_eq!(texture("5S 5D 2H").pairing, Pairing::Paired);
+    }
+
+    #[test]
+    fn test_flush_boards() {
+        let board = texture("KS QS 2S");
+        assert_eq!(board.suits, SuitTexture::Monotone);
+        assert!(board.flush_possible);
+        assert_eq!(board.flush_draws, vec![FlushDraw { suit: Suit::Spades, hole_cards_needed: 1 }]);
+
+        // Four spades: one spade already makes the flush
+        assert_eq!(texture("KS QS 7S 2S").suits, SuitTexture::Monotone);
+        assert!(texture("KS QS 7S 2S").flush_draws.is_empty());
+
+        // Three of a suit with an off-suit card isn't monotone
+        let board = texture("KS QS 7S 2D");
+        assert_eq!(board.suits, SuitTexture::FlushPossible);
+        assert!(board.flush_possible);
+        assert_eq!(board.wetness, texture("KS QS 7S").wetness);
+
+        // No draws left on the river
+        let river = texture("9H 8H 7C 2D 3S");
+        assert!(river.straight_possible);
+        assert!(river.flush_draws.is_empty() && river.straight_draws.is_empty());
+    }
+
+    #[test]
+    fn test_straight_on_the_board() {
+        let board = texture("5H 6D 7C 8S 9H");
+        assert!(board.straight_possible);
+        assert_eq!(board.connectedness, Connectedness::Connected);
+        // Every holding plays the straight at least
+        assert_eq!(board.straight_combos, 1081);
+        assert_eq!(board.wetness, Wetness::Wet);
+    }
+
+    #[test]
+    fn test_straight_combos_match_enumeration() {
+        // Best hand of every holding, from every five of its cards with the board
+        let brute_force = |board: &[Card]| -> usize {
+            let mut deck = Deck::new();
+            deck.remove(board).unwrap();
+            let mut count = 0;
+            for_each_combination(deck.cards(), 2, |holding| {
+                let cards: Vec<Card> = board.iter().chain(holding).copied().collect();
+                let mut best = None;
+                for_each_combination(&cards, 5, |five| {
+                    best = best.max(Some(HandValue::from_cards(five)));
+                });
+                if best.unwrap().hand_type() == HandType::Straight {
+                    count += 1;
+                }
+            });
+            count
+        };
+
+        // Pairs and board ranks in the hole: TT and T9 make the turn straight
+        for board in ["9H 8H 7C", "9H 8D 7C 6S", "9S 8S 7S", "AH 2D 3C", "9H 9D 8C 7S", "KS QS JS 2S 3D"] {
+            let board = parse_cards(board).unwrap();
+            assert_eq!(BoardTexture::classify(&board).unwrap().straight_combos, brute_force(&board));
+        }
+    }
+
+    #[test]
+    fn test_invalid_boards() {
+        assert!(BoardTexture::classify(&parse_cards("AH KD").unwrap()).is_err());
+        assert!(BoardTexture::classify(&parse_cards("AH KD 2C 3C 4C 5C").unwrap()).is_err());
+        let mut board = parse_cards("AH KD 2C").unwrap();
+        board.push(board[0]);
+        assert!(BoardTexture::classify(&board).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+use super::card::{Card, HandType, Rank};
+
+// Packed strength of the best poker hand that can be made from a set of cards.
+// The hand type sits in the high bits, followed by up to five tie-break ranks,
+// so two values compare exactly like the hands they were built from.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub struct HandValue(u32);
+
+impl HandValue {
+    // Accepts any number of cards: with five or more the best five-card hand is
+    // used, with fewer (e.g. a three-card OFC top row) only pairs and trips count
+    pub fn from_cards(cards: &[Card]) -> HandValue {
+        let mut rank_counts = [0u8; 13];
+        let mut suit_masks = [0u16; 4];
+        let mut rank_mask = 0u16;
+
+        for card in cards {
+            let rank_index = card.rank as usize - 2; // Rank::Two starts at 2
+            rank_counts[rank_index] += 1;
+            suit_masks[card.suit as usize] |= 1 << rank_index;
+            rank_mask |= 1 << rank_index;
+        }
+
+        let flush_mask = if cards.len() >= 5 {
+            suit_masks.iter().copied().find(|mask| mask.count_ones() >= 5)
+        } else {
+            None
+        };
+
+        if let Some(mask) = flush_mask {
+            if let Some(high) = straight_high(mask) {
+                if high == 14 {
+                    return pack(HandType::RoyalFlush, &[high], 0, 0);
+                }
+                return pack(HandType::StraightFlush, &[high], 0, 0);
+            }
+        }
+
+        // Group ranks by how many times they appear, highest ranks first
+        let mut quads = 0u16;
+        let mut trips = 0u16;
+        let mut pairs = 0u16;
+        for (rank_index, &count) in rank_counts.iter().enumerate() {
+            match count {
+                0 | 1 => {}
+                2 => pairs |= 1 << rank_index,
+                3 => trips |= 1 << rank_index,
+                _ => quads |= 1 << rank_index,
+            }
+        }
+
+        if quads != 0 {
+            let quad = highest(quads);
+            return pack(HandType::FourOfAKind, &[quad], rank_mask & !bit(quad), 1);
+        }
+
+        if trips != 0 {
+            let trip = highest(trips);
+            let filler = (trips | pairs) & !bit(trip);
+            if filler != 0 {
+                return pack(HandType::FullHouse, &[trip, highest(filler)], 0, 0);
+            }
+        }
+
+        if let Some(mask) = flush_mask {
+            return pack(HandType::Flush, &[], mask, 5);
+        }
+
+        if cards.len() >= 5 {
+            if let Some(high) = straight_high(rank_mask) {
+                return pack(HandType::Straight, &[high], 0, 0);
+            }
+        }
+
+        if trips != 0 {
+            let trip = highest(trips);
+            return pack(HandType::ThreeOfAKind, &[trip], rank_mask & !bit(trip), 2);
+        }
+
+        if pairs.count_ones() >= 2 {
+            let high_pair = highest(pairs);
+            let low_pair = highest(pairs & !bit(high_pair));
+            let kicker_mask = rank_mask & !bit(high_pair) & !bit(low_pair);
+            return pack(HandType::TwoPair, &[high_pair, low_pair], kicker_mask, 1);
+        }
+
+        if pairs != 0 {
+            let pair = highest(pairs);
+            return pack(HandType::OnePair, &[pair], rank_mask & !bit(pair), 3);
+        }
+
+        pack(HandType::HighCard, &[], rank_mask, 5)
+    }
+
+    pub fn hand_type(&self) -> HandType {
+        HandType::ALL[(self.0 >> 20) as usize]
+    }
+
+    // Rank that defines the hand: the pair, trips or quads, the higher pair of
+    // two pair, the trips of a full house, or the highest card otherwise
+    pub fn leading_rank(&self) -> Rank {
+        Rank::ALL[((self.0 >> 16) & 0xF) as usize - 2]
+    }
+
+    // Raw packed value, stable across runs, for use as a table key
+    pub fn as_u32(&self) -> u32 {
+        self.0
+    }
+}
+
+// Packs the hand type, the ranks that define it, then the highest `kickers`
+// ranks left in `kicker_mask`
+fn pack(hand_type: HandType, leading: &[u8], mut kicker_mask: u16, kickers: usize) -> HandValue {
+    let mut value = (hand_type as u32) << 20;
+    let mut shift = 16;
+    for &rank in leading {
+        value |= (rank as u32) << shift;
+        shift -= 4;
+    }
+    for _ in 0..kickers {
+        if kicker_mask == 0 {
+            break;
+        }
+        let rank = highest(kicker_mask);
+        value |= (rank as u32) << shift;
+        kicker_mask &= !bit(rank);
+        shift -= 4;
+    }
+    HandValue(value)
+}
+
+fn bit(rank: u8) -> u16 {
+    1 << (rank - 2)
+}
+
+// Highest rank value (2..=14) present in a non-empty rank mask
+fn highest(mask: u16) -> u8 {
+    (15 - mask.leading_zeros()) as u8 + 2
+}
+
+// Value of the highest card of a straight contained in the mask, if any.
+// The ace also plays low, in which case the straight is five-high.
+fn straight_high(mask: u16) -> Option<u8> {
+    let bits = ((mask as u32) << 1) | ((mask as u32 >> 12) & 1);
+    (4..=13u32)
+        .rev()
+        .find(|&top| (bits >> (top - 4)) & 0x1F == 0x1F)
+        .map(|top| top as u8 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::parse_cards;
+
+    fn value(cards: &str) -> HandValue {
+        HandValue::from_cards(&parse_cards(cards).unwrap())
+    }
+
+    #[test]
+    fn test_five_card_hand_types() {
+        assert_eq!(value("AS KS QS JS 10S").hand_type(), HandType::RoyalFlush);
+        assert_eq!(value("9S 8S 7S 6S 5S").hand_type(), HandType::StraightFlush);
+        assert_eq!(value("AS 2S 3S 4S 5S").hand_type(), HandType::StraightFlush);
+        assert_eq!(value("AH AD AC AS KH").hand_type(), HandType::FourOfAKind);
+        assert_eq!(value("AH AD AC KH KD").hand_type(), HandType::FullHouse);
+        assert_eq!(value("AH 3H 5H 7H 9H").hand_type(), HandType::Flush);
+        assert_eq!(value("AC 2D 3H 4S 5C").hand_type(), HandType::Straight);
+        assert_eq!(value("AH AD AC 2H 3D").hand_type(), HandType::ThreeOfAKind);
+        assert_eq!(value("AH AD KH KD 2C").hand_type(), HandType::TwoPair);
+        assert_eq!(value("AH AD 2C 3S 4H").hand_type(), HandType::OnePair);
+        assert_eq!(value("AH KD 2C 3S 4H").hand_type(), HandType::HighCard);
+    }
+
+    #[test]
+    fn test_wheel_is_the_lowest_straight() {
+        assert!(value("AC 2D 3H 4S 5C") < value("2C 3D 4H 5S 6C"));
+        assert!(value("AS 2S 3S 4S 5S") < value("2H 3H 4H 5H 6H"));
+        assert_eq!(value("AC 2D 3H 4S 5C").leading_rank(), Rank::Five);
+    }
+
+    #[test]
+    fn test_tie_breaks_follow_rank_groups() {
+        // Two pair is decided by the pairs before the kicker
+        assert!(value("3H 3D 2C 2S AH") < value("4H 4D 2D 2H 3C"));
+        assert!(value("KH KD 2C 2S AH") > value("KC KS 2D 2H QC"));
+        // Full house is decided by the trips before the pair
+        assert!(value("AH AD AC 2H 2D") > value("KH KD KC AS AC"));
+        assert_eq!(value("2H 2D KC KS KD").leading_rank(), Rank::King);
+        assert_eq!(value("AH KH QH JH 10H"), value("AD KD QD JD 10D"));
+    }
+
+    #[test]
+    fn test_best_five_of_seven() {
+        // Two sets of trips make a full house
+        let hand = value("AH AD AC KH KD KC 2S");
+        assert_eq!(hand, value("AH AD AC KH KD"));
+        // Third pair can still play as the kicker
+        assert_eq!(value("AH AD KH KD QH QD 2S"), value("AH AD KH KD QC"));
+        // Straight flush beats a higher straight in the same cards
+        let hand = value("9H 8H 7H 6H 5H 10D JC");
+        assert_eq!(hand.hand_type(), HandType::StraightFlush);
+        assert_eq!(hand, value("9H 8H 7H 6H 5H"));
+        // Only the five best suited cards count towards a flush
+        assert_eq!(value("AH KH 9H 7H 5H 3H 2H"), value("AH KH 9H 7H 5H"));
+    }
+
+    #[test]
+    fn test_fewer_than_five_cards() {
+        assert_eq!(value("QH QD 2C").hand_type(), HandType::OnePair);
+        assert_eq!(value("QH QD QC").hand_type(), HandType::ThreeOfAKind);
+        assert_eq!(value("AH KH QH").hand_type(), HandType::HighCard);
+        // A three-card row is weaker than the same pair with more kickers
+        assert!(value("AH AD KC") < value("AS AC KD 2H 3S"));
+    }
+}
@@ -0,0 +1,332 @@
+use super::card::{Card, HandType, Rank};
+use super::deck::Deck;
+use super::rng::Rng;
+use super::value::HandValue;
+
+// Rules used to decide which line of the paytable a final hand hits
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Game {
+    JacksOrBetter,
+    BonusPoker,
+    DeucesWild,
+}
+
+// A line of a paytable. Most lines are plain hand types, the others cover the
+// distinctions some games pay differently that `HandType` can't express.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Payline {
+    Hand(HandType),
+    // One pair of jacks, queens, kings or aces
+    JacksOrBetter,
+    FourAces,
+    FourTwosThroughFours,
+    FourFivesThroughKings,
+    // Deuces Wild lines
+    NaturalRoyalFlush,
+    FourDeuces,
+    WildRoyalFlush,
+    FiveOfAKind,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Paytable {
+    pub name: String,
+    pub game: Game,
+    // Credits paid per coin bet, at the maximum bet
+    pub pays: Vec<(Payline, u32)>,
+}
+
+impl Paytable {
+    pub fn new(name: &str, game: Game, pays: Vec<(Payline, u32)>) -> Self {
+        Paytable { name: name.to_string(), game, pays }
+    }
+
+    // Full pay 9/6 Jacks or Better
+    pub fn jacks_or_better() -> Self {
+        Paytable::new(
+            "Jacks or Better 9/6",
+            Game::JacksOrBetter,
+            vec![
+                (Payline::Hand(HandType::RoyalFlush), 800),
+                (Payline::Hand(HandType::StraightFlush), 50),
+                (Payline::Hand(HandType::FourOfAKind), 25),
+                (Payline::Hand(HandType::FullHouse), 9),
+                (Payline::Hand(HandType::Flush), 6),
+                (Payline::Hand(HandType::Straight), 4),
+                (Payline::Hand(HandType::ThreeOfAKind), 3),
+                (Payline::Hand(HandType::TwoPair), 2),
+                (Payline::JacksOrBetter, 1),
+            ],
+        )
+    }
+
+    // 8/5 Bonus Poker
+    pub fn bonus_poker() -> Self {
+        Paytable::new(
+            "Bonus Poker 8/5",
+            Game::BonusPoker,
+            vec![
+                (Payline::Hand(HandType::RoyalFlush), 800),
+                (Payline::Hand(HandType::StraightFlush), 50),
+                (Payline::FourAces, 80),
+                (Payline::FourTwosThroughFours, 40),
+                (Payline::FourFivesThroughKings, 25),
+                (Payline::Hand(HandType::FullHouse), 8),
+                (Payline::Hand(HandType::Flush), 5),
+                (Payline::Hand(HandType::Straight), 4),
+                (Payline::Hand(HandType::ThreeOfAKind), 3),
+                (Payline::Hand(HandType::TwoPair), 2),
+                (Payline::JacksOrBetter, 1),
+            ],
+        )
+    }
+
+    // Full pay Deuces Wild
+    pub fn deuces_wild() -> Self {
+        Paytable::new(
+            "Deuces Wild (full pay)",
+            Game::DeucesWild,
+            vec![
+                (Payline::NaturalRoyalFlush, 800),
+                (Payline::FourDeuces, 200),
+                (Payline::WildRoyalFlush, 25),
+                (Payline::FiveOfAKind, 15),
+                (Payline::Hand(HandType::StraightFlush), 9),
+                (Payline::Hand(HandType::FourOfAKind), 5),
+                (Payline::Hand(HandType::FullHouse), 3),
+                (Payline::Hand(HandType::Flush), 2),
+                (Payline::Hand(HandType::Straight), 2),
+                (Payline::Hand(HandType::ThreeOfAKind), 1),
+            ],
+        )
+    }
+
+    // Most specific line the five cards make under this paytable's game
+    pub fn classify(&self, cards: &[Card]) -> Payline {
+        match self.game {
+            Game::JacksOrBetter => classify_natural(cards, false),
+            Game::BonusPoker => classify_natural(cards, true),
+            Game::DeucesWild => classify_deuces_wild(cards),
+        }
+    }
+
+    // Credits per coin for a final hand, zero if its line isn't on the paytable
+    pub fn payout(&self, cards: &[Card]) -> u32 {
+        let line = self.classify(cards);
+        self.pays
+            .iter()
+            .find(|(payline, _)| *payline == line)
+            .map_or(0, |&(_, pay)| pay)
+    }
+}
+
+fn classify_natural(cards: &[Card], bonus_quads: bool) -> Payline {
+    let value = HandValue::from_cards(cards);
+    let rank = value.leading_rank();
+
+    match value.hand_type() {
+        HandType::OnePair if rank >= Rank::Jack => Payline::JacksOrBetter,
+        HandType::FourOfAKind if bonus_quads => match rank {
+            Rank::Ace => Payline::FourAces,
+            Rank::Two | Rank::Three | Rank::Four => Payline::FourTwosThroughFours,
+            _ => Payline::FourFivesThroughKings,
+        },
+        hand_type => Payline::Hand(hand_type),
+    }
+}
+
+// Deuces substitute for whatever card makes the best hand
+fn classify_deuces_wild(cards: &[Card]) -> Payline {
+    let wilds = cards.iter().filter(|card| card.rank == Rank::Two).count();
+    let naturals: Vec<&Card> = cards.iter().filter(|card| card.rank != Rank::Two).collect();
+
+    if wilds == 0 {
+        return match HandValue::from_cards(cards).hand_type() {
+            HandType::RoyalFlush => Payline::NaturalRoyalFlush,
+            hand_type => Payline::Hand(hand_type),
+        };
+    }
+    if wilds == 4 {
+        return Payline::FourDeuces;
+    }
+
+    let mut counts = [0usize; 15];
+    for card in &naturals {
+        counts[card.rank as usize] += 1;
+    }
+    let most_of_a_kind = counts.iter().copied().max().unwrap_or(0) + wilds;
+    let pairs = counts.iter().filter(|&&count| count == 2).count();
+    let distinct = counts.iter().all(|&count| count <= 1);
+    let suited = naturals.windows(2).all(|pair| pair[0].suit == pair[1].suit);
+
+    // Five-high up to ace-high, the ace playing low in the wheel
+    let fits_straight = distinct
+        && (5..=14).any(|high: usize| {
+            naturals.iter().all(|card| {
+                let rank = card.rank as usize;
+                (rank <= high && rank + 4 >= high) || (high == 5 && rank == 14)
+            })
+        });
+    let royal_ranks = naturals.iter().all(|card| card.rank >= Rank::Ten);
+
+    if suited && distinct && royal_ranks {
+        Payline::WildRoyalFlush
+    } else if most_of_a_kind >= 5 {
+        Payline::FiveOfAKind
+    } else if suited && fits_straight {
+        Payline::Hand(HandType::StraightFlush)
+    } else if most_of_a_kind == 4 {
+        Payline::Hand(HandType::FourOfAKind)
+    } else if wilds == 1 && pairs == 2 {
+        Payline::Hand(HandType::FullHouse)
+    } else if suited {
+        Payline::Hand(HandType::Flush)
+    } else if fits_straight {
+        Payline::Hand(HandType::Straight)
+    } else if most_of_a_kind == 3 {
+        Payline::Hand(HandType::ThreeOfAKind)
+    } else {
+        Payline::Hand(HandType::OnePair)
+    }
+}
+
+// A single video poker machine: deal five cards, hold some, draw the rest
+#[derive(Debug, Clone)]
+pub struct VideoPoker {
+    pub paytable: Paytable,
+    rng: Rng,
+    deck: Deck,
+    hand: Vec<Card>,
+    drawn: bool,
+}
+
+impl VideoPoker {
+    pub fn new(paytable: Paytable, seed: u64) -> Self {
+        VideoPoker {
+            paytable,
+            rng: Rng::new(seed),
+            deck: Deck::new(),
+            hand: Vec::new(),
+            drawn: false,
+        }
+    }
+
+    // Shuffles a full deck and deals a new five-card hand
+    pub fn deal(&mut self) -> &[Card] {
+        self.deck = Deck::new();
+        self.deck.shuffle(&mut self.rng);
+        self.hand = self.deck.deal_many(5).expect("A full deck has enough cards");
+        self.drawn = false;
+        &self.hand
+    }
+
+    // Replaces every card that isn't held with a card from the same deck
+    pub fn draw(&mut self, hold: [bool; 5]) -> Result<&[Card], &'static str> {
+        if self.hand.is_empty() {
+            return Err("No hand has been dealt");
+        }
+        if self.drawn {
+            return Err("The hand has already been drawn");
+        }
+
+        for (card, &held) in self.hand.iter_mut().zip(hold.iter()) {
+            if !held {
+                *card = self.deck.deal().ok_or("The deck is empty")?;
+            }
+        }
+        self.drawn = true;
+        Ok(&self.hand)
+    }
+
+    pub fn hand(&self) -> &[Card] {
+        &self.hand
+    }
+
+    // Credits won by the current hand for the given number of coins
+    pub fn payout(&self, coins: u32) -> u32 {
+        if self.hand.is_empty() {
+            return 0;
+        }
+        self.paytable.payout(&self.hand) * coins
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::parse_cards;
+
+    fn pay(paytable: &Paytable, cards: &str) -> u32 {
+        paytable.payout(&parse_cards(cards).unwrap())
+    }
+
+    #[test]
+    fn test_jacks_or_better_payouts() {
+        let paytable = Paytable::jacks_or_better();
+        assert_eq!(pay(&paytable, "AS KS QS JS 10S"), 800);
+        assert_eq!(pay(&paytable, "AH AD AC AS KH"), 25);
+        assert_eq!(pay(&paytable, "AH AD AC KH KD"), 9);
+        assert_eq!(pay(&paytable, "AH AD KH KD 2C"), 2);
+        assert_eq!(pay(&paytable, "JH JD 2C 3S 4H"), 1);
+        assert_eq!(pay(&paytable, "10H 10D 2C 3S 4H"), 0);
+        assert_eq!(pay(&paytable, "AH KD 2C 3S 4H"), 0);
+    }
+
+    #[test]
+    fn test_bonus_poker_quads() {
+        let paytable = Paytable::bonus_poker();
+        assert_eq!(pay(&paytable, "AH AD AC AS KH"), 80);
+        assert_eq!(pay(&paytable, "3H 3D 3C 3S KH"), 40);
+        assert_eq!(pay(&paytable, "9H 9D 9C 9S KH"), 25);
+        assert_eq!(pay(&paytable, "QH QD 2C 3S 4H"), 1);
+    }
+
+    #[test]
+    fn test_deuces_wild_classification() {
+        let paytable = Paytable::deuces_wild();
+        let line = |cards: &str| paytable.classify(&parse_cards(cards).unwrap());
+
+        assert_eq!(line("AS KS QS JS 10S"), Payline::NaturalRoyalFlush);
+        assert_eq!(line("2S 2H 2D 2C 7H"), Payline::FourDeuces);
+        assert_eq!(line("AS KS 2D JS 10S"), Payline::WildRoyalFlush);
+        assert_eq!(line("9S 9H 9D 2C 2H"), Payline::FiveOfAKind);
+        assert_eq!(line("2S 3H 4H 6H 7H"), Payline::Hand(HandType::StraightFlush));
+        assert_eq!(line("AH 2S 3H 4H 5H"), Payline::Hand(HandType::StraightFlush));
+        assert_eq!(line("9S 9H 2D KC 4H"), Payline::Hand(HandType::ThreeOfAKind));
+        assert_eq!(line("9S 9H 2D KC KH"), Payline::Hand(HandType::FullHouse));
+        assert_eq!(line("9S 9H 2D 2C KH"), Payline::Hand(HandType::FourOfAKind));
+        assert_eq!(line("2S 5H 7H 9H KH"), Payline::Hand(HandType::Flush));
+        assert_eq!(line("2S 5H 6D 8C 9H"), Payline::Hand(HandType::Straight));
+        assert_eq!(line("2S 5H 7D 9C KH"), Payline::Hand(HandType::OnePair));
+
+        assert_eq!(pay(&paytable, "2S 5H 7D 9C KH"), 0);
+        assert_eq!(pay(&paytable, "AH AD KH KD 3C"), 0);
+        assert_eq!(pay(&paytable, "9S 9H 2D KC 4H"), 1);
+    }
+
+    #[test]
+    fn test_deal_and_draw() {
+        let mut machine = VideoPoker::new(Paytable::jacks_or_better(), 42);
+        assert!(machine.draw([false; 5]).is_err());
+
+        let dealt = machine.deal().to_vec();
+        assert_eq!(dealt.len(), 5);
+
+        let hold = [true, false, true, false, false];
+        let drawn = machine.draw(hold).unwrap().to_vec();
+        assert_eq!(drawn[0], dealt[0]);
+        assert_eq!(drawn[2], dealt[2]);
+        for i in [1, 3, 4] {
+            assert!(!dealt.contains(&drawn[i]));
+        }
+        assert!(machine.draw(hold).is_err());
+        assert_eq!(machine.payout(5), machine.paytable.payout(&drawn) * 5);
+    }
+
+    #[test]
+    fn test_same_seed_same_deal() {
+        let mut first = VideoPoker::new(Paytable::deuces_wild(), 7);
+        let mut second = VideoPoker::new(Paytable::deuces_wild(), 7);
+        assert_eq!(first.deal(), second.deal());
+    }
+}
@@ -4,7 +4,7 @@ use std::str;
 #[test]
 fn test_cli_with_royal_flush_vs_four_of_a_kind() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "AS KS QS JS 10S", "AH AD AC AS KH"])
+        .args(["run", "--", "AS KS QS JS 10S", "AH AD AC AS KH"])
         .output()
         .expect("Failed to execute command");
     
@@ -20,7 +20,7 @@ fn test_cli_with_royal_flush_vs_four_of_a_kind() {
 #[test]
 fn test_cli_with_pair_vs_pair() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "AH AD 2C 3S 4H", "KH KD 2S 3C 4D"])
+        .args(["run", "--", "AH AD 2C 3S 4H", "KH KD 2S 3C 4D"])
         .output()
         .expect("Failed to execute command");
     
@@ -36,7 +36,7 @@ fn test_cli_with_pair_vs_pair() {
 #[test]
 fn test_cli_with_tie() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "AH KH QH JH 10H", "AD KD QD JD 10D"])
+        .args(["run", "--", "AH KH QH JH 10H", "AD KD QD JD 10D"])
         .output()
         .expect("Failed to execute command");
     
@@ -52,7 +52,7 @@ fn test_cli_with_tie() {
 #[test]
 fn test_cli_with_invalid_hand() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "AH AD", "KH KD 2S 3C 4D"])
+        .args(["run", "--", "AH AD", "KH KD 2S 3C 4D"])
         .output()
         .expect("Failed to execute command");
     
@@ -68,7 +68,7 @@ fn test_cli_with_invalid_hand() {
 #[test]
 fn test_cli_with_no_arguments() {
     let output = Command::new("cargo")
-        .args(&["run"])
+        .args(["run"])
         .output()
         .expect("Failed to execute command");
     
@@ -79,4 +79,440 @@ fn test_cli_with_no_arguments() {
     
     assert!(stdout.contains("Poker Hand Evaluator"));
     assert!(stdout.contains("No hands provided as arguments"));
-} 
\ No newline at end of file
+} 
+
+#[test]
+fn test_cli_deal_random_hands() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "deal", "3", "--seed", "42"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("Seed: 42"));
+    let hands: Vec<&str> = stdout.lines().skip(2).collect();
+    assert_eq!(hands.len(), 3);
+    for hand in hands {
+        assert!(exam::poker::parse_hand(hand).is_ok());
+    }
+}
+
+#[test]
+fn test_cli_deal_is_reproducible_with_seed() {
+    let run = || {
+        let output = Command::new("cargo")
+            .args(["run", "--", "deal", "5", "--seed", "7", "--evaluate"])
+            .output()
+            .expect("Failed to execute command");
+        String::from_utf8(output.stdout).unwrap()
+    };
+    
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn test_cli_deal_with_excluded_cards_and_ranking() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "deal", "20", "--seed", "1", "--exclude", "AS AH AD AC", "--rank"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("Ranking:"));
+    assert!(stdout.contains("1. "));
+    // No excluded ace was dealt
+    for line in stdout.lines().skip(2) {
+        assert!(line.split_whitespace().all(|card| !card.starts_with('A')));
+    }
+}
+
+#[test]
+fn test_cli_deal_with_invalid_count() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "deal", "many"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    assert!(stdout.contains("Error: deal expects the number of hands to deal"));
+}
+
+#[test]
+fn test_cli_equity() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "equity", "AS AH", "KS KH", "--board", "2C 7D 9H", "--iterations", "2000", "--seed", "3"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("Seed: 3"));
+    assert!(stdout.contains("Iterations: 2000"));
+    assert!(stdout.contains("Player 1 (AS AH): win"));
+    assert!(stdout.contains("Player 2 (KS KH): win"));
+}
+
+#[test]
+fn test_cli_equity_with_conflicting_cards() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "equity", "AS AH", "AS KH"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    assert!(stdout.contains("Error: Duplicate card"));
+}
+
+#[test]
+fn test_cli_exact_equity() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "equity", "AS AH", "KS KH", "--board", "2C 7D 9H 3S", "--exact"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("Boards: 44"));
+    assert!(stdout.contains("Player 2 (KS KH): win 4.55%"));
+    assert!(stdout.contains("(± 0.00%)"));
+}
+
+#[test]
+fn test_cli_range_equity() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "range-equity", "AsAh", "KK, QsQh:0.5", "--board", "2C 7D 9H 3S", "--exact"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("Boards: 308"));
+    assert!(stdout.contains("Range 1 (AhAs): win"));
+    assert!(stdout.contains("Range 2 (KK, QhQs:0.5): win"));
+    assert!(stdout.contains("AS AH: equity"));
+}
+
+#[test]
+fn test_cli_outs() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "outs", "AH KH", "--board", "2H 7H 9C"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("Outs: 15 of 47 unseen cards"));
+    assert!(stdout.contains("Flush (9):"));
+    assert!(stdout.contains("OnePair (6): KD AD KC AC KS AS"));
+    assert!(stdout.contains("Next card: 31.91%"));
+    assert!(stdout.contains("By the river: 54.12%"));
+}
+
+#[test]
+fn test_cli_draw() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "draw", "2H 5H 9H KH 7C", "--against", "AS AD 3C 4C 8S", "--top", "3"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("1. Discard 7C: EV 1.3571 (Jacks or Better 9/6) - beats opponent 21.43%"));
+    assert!(stdout.contains("Flush 21.43%"));
+    assert!(!stdout.contains("4. Discard"));
+}
+
+#[test]
+fn test_cli_preflop_table() {
+    let path = std::env::temp_dir().join(format!("preflop-cli-test-{}.bin", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "preflop", "generate", path, "--trials", "20", "--seed", "4", "--threads", "2"])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    assert!(stdout.contains("Seed: 4"));
+    assert!(stdout.contains("Wrote 20 trials per matchup"));
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "preflop", path, "AKs", "QQ"])
+        .output()
+        .expect("Failed to execute command");
+    std::fs::remove_file(path).unwrap();
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("AKs vs QQ: "));
+}
+
+#[test]
+fn test_cli_texture() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "texture", "9H 8H 7C"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("Suits: TwoTone"));
+    assert!(stdout.contains("Wetness: Wet"));
+    assert!(stdout.contains("Flush draw: 2 hole card(s) of Hearts"));
+    assert!(stdout.contains("Straight draw (open-ended): holding [Ten], completed by [Jack, Six]"));
+}
+
+#[test]
+fn test_cli_nuts() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "nuts", "2C 7D 9H JS KD", "--holding", "KS KH", "--top", "3"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("1. Straight - 16 combos: QT"));
+    assert!(stdout.contains("3. ThreeOfAKind - 3 combos: KhKc, KhKs, KcKs"));
+    assert!(stdout.contains("KS KH: 3rd nuts, beaten by 32 combos"));
+}
+
+#[test]
+fn test_cli_percentile() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "percentile", "AS AH AD AC KS"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("Value rank: 11 of 7462"));
+    assert!(stdout.contains("Better hands: 40 - equal hands: 3 - worse hands: 2598916"));
+}
+
+#[test]
+fn test_cli_vs_random() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "vs-random", "AS AH", "4", "--iterations", "5000", "--seed", "7"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("Seed: 7"));
+    assert!(stdout.contains("AS AH against 4 random hands: win"));
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "vs-random", "table", "AA"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("1 opponent: win 84.89% - tie 0.55%"));
+    assert!(stdout.contains("9 opponents: win 30.88% - tie 0.50%"));
+}
+
+#[test]
+fn test_cli_strength() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "strength", "AD QC", "--board", "3H 4C JH"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("HS: 58.51%"));
+    assert!(stdout.contains("PPot: 20.83%"));
+    assert!(stdout.contains("NPot: 27.37%"));
+}
+
+#[test]
+fn test_cli_odds() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "odds", "150", "50", "--hole", "AH 5H", "--villain", "7S 7C", "--board", "KH 7H 2C 9S"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("Pot odds: 3.00 to 1"));
+    assert!(stdout.contains("Required equity: 25.00%"));
+    assert!(stdout.contains("Minimum defence frequency: 66.67%"));
+    assert!(stdout.contains("Equity: 15.91%"));
+    assert!(stdout.contains("Fold"));
+}
+
+#[test]
+fn test_cli_icm() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "icm", "0,2450,2400,2500", "50,30,20", "--hero", "3", "--villain", "1", "--pot", "2650", "--to-call", "2400", "--equity", "0.5"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("Player 1 (0): "));
+    assert!(stdout.contains("Equity: 50.00%"));
+    assert!(stdout.contains("(chip EV: 47.52%)"));
+    assert!(stdout.contains("Fold\n"));
+}
+
+#[test]
+fn test_cli_push_fold() {
+    let path = std::env::temp_dir().join(format!("push-fold-cli-test-{}.bin", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    Command::new("cargo")
+        .args(["run", "--", "preflop", "generate", path, "--trials", "20", "--seed", "4"])
+        .output()
+        .expect("Failed to execute command");
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "push-fold", path, "10", "--ante", "0.1"])
+        .output()
+        .expect("Failed to execute command");
+    std::fs::remove_file(path).unwrap();
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("Stack: 10 BB - ante: 0.1 BB"));
+    assert!(stdout.contains("Small blind shoves "));
+    assert!(stdout.contains("Big blind calls "));
+    assert!(stdout.contains("  AA AKs AQs"));
+}
+
+#[test]
+fn test_cli_starting() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "starting", "AS KS"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+    
+    assert!(stdout.contains("AS KS (AKs)"));
+    assert!(stdout.contains("Chen score: 12"));
+    assert!(stdout.contains("Sklansky group: 1"));
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "starting", "--top", "2"])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    println!("Sortie: {}", stdout);
+
+    assert!(stdout.contains("   1  AA     85.17%    20      1"));
+    assert!(stdout.contains("   2  KK"));
+    assert!(!stdout.contains("   3  "));
+}
+
+#[test]
+fn test_canonical() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "canonical", "AS KS", "--board", "2S 7H 9D"])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+
+    assert!(stdout.contains("Canonical: KH AH | 2H 7D 9C"));
+    assert!(stdout.contains("of 1286792"));
+}
+
+#[test]
+fn test_cli_buckets() {
+    let path = std::env::temp_dir().join(format!("buckets-cli-test-{}.bin", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "buckets", "train", path, "3", "3", "--hands", "40", "--runouts", "10", "--seed", "2"])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    assert!(stdout.contains("Seed: 2"));
+    assert!(stdout.contains("Bucket 2: "));
+    assert!(stdout.contains("Wrote 3 buckets"));
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "buckets", path, "KS KC", "--board", "KH 7H 2C"])
+        .output()
+        .expect("Failed to execute command");
+    std::fs::remove_file(path).unwrap();
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+
+    assert!(stdout.contains("Bucket 2 of 3"));
+}
+
+#[test]
+fn test_cli_combos() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "combos", "AKo", "--board", "AS 7H 2C", "--hole", "KD QD"])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+
+    // Afficher la sortie pour le débogage
+    println!("Sortie: {}", stdout);
+
+    assert!(stdout.contains("AKo: 12 combos, 9 with the board"));
+    assert!(stdout.contains("Combos: 7"));
+    assert!(stdout.contains("Blocked by hole cards: 2 (22.22%)"));
+}
@@ -1,4 +1,4 @@
-use exam::poker::{parse_hand, HandType};
+use exam::poker::{parse_hand, HandType, OfcHand};
 
 #[test]
 fn test_parse_and_evaluate_royal_flush() {
@@ -116,4 +116,31 @@ fn test_tie_hands() {
     let royal_flush_diamonds = parse_hand("AD KD QD JD 10D").unwrap();
     
     assert_eq!(royal_flush_hearts.cmp(&royal_flush_diamonds), std::cmp::Ordering::Equal);
-} 
\ No newline at end of file
+} 
+
+#[test]
+fn test_compare_two_pair_by_pairs_before_kicker() {
+    let fours_and_twos = parse_hand("4H 4D 2D 2H 3C").unwrap();
+    let threes_and_twos_ace_kicker = parse_hand("3H 3D 2C 2S AH").unwrap();
+    
+    assert!(fours_and_twos > threes_and_twos_ace_kicker);
+}
+
+#[test]
+fn test_compare_wheel_with_six_high_straight() {
+    let wheel = parse_hand("AC 2D 3H 4S 5C").unwrap();
+    let six_high = parse_hand("2C 3D 4H 5S 6C").unwrap();
+    
+    assert!(six_high > wheel);
+}
+
+#[test]
+fn test_ofc_scoring() {
+    let player1 = OfcHand::parse("QH QD 2C", "5S 5C 5D 8C KS", "AH 3H 6H 9H JH").unwrap();
+    let player2 = OfcHand::parse("AS AD 3C", "6S 6C 8D 9C KD", "10S 10D JC JD 2S").unwrap();
+    
+    assert!(!player1.is_foul());
+    assert!(player2.is_foul());
+    assert!(player1.qualifies_for_fantasyland());
+    assert_eq!(player1.score_against(&player2), 6 + 13);
+}